@@ -0,0 +1,305 @@
+//! Position markers that follow the items they point at as a list is edited.
+//!
+//! An [`Anchors`] set is an [`EditObserver`], so it can be passed to
+//! [`edit_observed`](crate::edit_observed) to keep its anchors up to date while editing a list:
+//!
+//! ```
+//! use editer::{
+//!     anchor::{Anchors, Gravity},
+//!     edit_observed,
+//! };
+//!
+//! let mut items = vec!['a', 'b', 'c', 'd', 'e'];
+//!
+//! let mut anchors = Anchors::new();
+//! let cursor = anchors.add(3, Gravity::Right);
+//! let error = anchors.add(1, Gravity::Right);
+//!
+//! edit_observed(&mut items, &mut anchors, |item| {
+//!     if item == 'a' {
+//!         item.insert_after(['x', 'y']);
+//!     } else if item == 'b' {
+//!         item.remove();
+//!     }
+//! });
+//!
+//! assert_eq!(items, vec!['a', 'x', 'y', 'c', 'd', 'e']);
+//!
+//! assert_eq!(anchors.get(cursor), Some(4));
+//! assert_eq!(items[4], 'd');
+//!
+//! assert_eq!(anchors.get(error), None);
+//! assert!(anchors.is_deleted(error));
+//! ```
+
+use crate::observer::EditObserver;
+use alloc::vec::Vec;
+
+/// Determines how an anchor moves when items are inserted at its position, or when the item it
+/// points at is replaced with more than one item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Gravity {
+    /// The anchor stays put when items are inserted at its position, so that it points at the
+    /// first inserted item. When its item is replaced, it points at the first replacement.
+    Left,
+
+    /// The anchor moves along with its item when items are inserted at its position. When its
+    /// item is replaced, it points at the last replacement.
+    Right,
+}
+
+/// A handle to an anchor in an [`Anchors`] set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Anchor(usize);
+
+/// A set of anchors, each marking a position in a list.
+///
+/// As an [`EditObserver`], an `Anchors` set shifts its anchors in response to changes to the list.
+/// When the item an anchor points at is removed, the anchor is deleted.
+#[derive(Clone, Debug, Default)]
+pub struct Anchors {
+    anchors: Vec<State>,
+}
+
+#[derive(Clone, Debug)]
+struct State {
+    index: Option<usize>,
+    gravity: Gravity,
+}
+
+impl Anchors {
+    /// Returns an empty set of anchors.
+    pub fn new() -> Anchors {
+        Anchors {
+            anchors: Vec::new(),
+        }
+    }
+
+    /// Adds an anchor at `index` with the given `gravity`, returning a handle to it.
+    pub fn add(&mut self, index: usize, gravity: Gravity) -> Anchor {
+        self.anchors.push(State {
+            index: Some(index),
+            gravity,
+        });
+
+        Anchor(self.anchors.len() - 1)
+    }
+
+    /// Returns the current index of `anchor`, or `None` if it's been deleted.
+    ///
+    /// Panics if `anchor` doesn't belong to this set.
+    pub fn get(&self, anchor: Anchor) -> Option<usize> {
+        self.anchors[anchor.0].index
+    }
+
+    /// Returns `true` if the item `anchor` pointed at has been removed.
+    ///
+    /// Panics if `anchor` doesn't belong to this set.
+    pub fn is_deleted(&self, anchor: Anchor) -> bool {
+        self.get(anchor).is_none()
+    }
+
+    /// Returns the gravity of `anchor`.
+    ///
+    /// Panics if `anchor` doesn't belong to this set.
+    pub fn gravity(&self, anchor: Anchor) -> Gravity {
+        self.anchors[anchor.0].gravity
+    }
+
+    /// Returns the number of anchors in the set, including deleted anchors.
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    /// Returns `true` if the set contains no anchors.
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    fn live(&mut self) -> impl Iterator<Item = (&mut usize, Gravity)> {
+        self.anchors
+            .iter_mut()
+            .filter_map(|state| state.index.as_mut().map(|index| (index, state.gravity)))
+    }
+}
+
+impl EditObserver for Anchors {
+    fn on_insert(&mut self, index: usize, count: usize) {
+        for (anchor, gravity) in self.live() {
+            if index < *anchor || (index == *anchor && gravity == Gravity::Right) {
+                *anchor += count;
+            }
+        }
+    }
+
+    fn on_remove(&mut self, index: usize) {
+        for state in &mut self.anchors {
+            match state.index {
+                Some(anchor) if anchor == index => state.index = None,
+                Some(anchor) if anchor > index => state.index = Some(anchor - 1),
+                _ => {}
+            }
+        }
+    }
+
+    fn on_replace(&mut self, index: usize, count: usize) {
+        if count == 0 {
+            return self.on_remove(index);
+        }
+
+        for (anchor, gravity) in self.live() {
+            if index < *anchor || (index == *anchor && gravity == Gravity::Right) {
+                *anchor += count - 1;
+            }
+        }
+    }
+
+    fn on_move(&mut self, from: usize, to: usize) {
+        for (anchor, _) in self.live() {
+            if *anchor == from {
+                *anchor = to;
+            } else if from < *anchor && *anchor <= to {
+                *anchor -= 1;
+            } else if to <= *anchor && *anchor < from {
+                *anchor += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Anchors, Gravity};
+    use crate::{edit_observed, observer::EditObserver, reconcile::reconcile_observed};
+
+    #[test]
+    fn inserting_before_an_anchor() {
+        let mut items = vec![1, 2, 3];
+
+        let mut anchors = Anchors::new();
+        let left = anchors.add(1, Gravity::Left);
+        let right = anchors.add(1, Gravity::Right);
+
+        edit_observed(&mut items, &mut anchors, |item| {
+            if item == 2 {
+                item.insert_before([4, 5]);
+            }
+        });
+
+        assert_eq!(items, vec![1, 4, 5, 2, 3]);
+        assert_eq!(anchors.get(left), Some(1));
+        assert_eq!(anchors.get(right), Some(3));
+    }
+
+    #[test]
+    fn inserting_after_an_anchor() {
+        let mut items = vec![1, 2, 3];
+
+        let mut anchors = Anchors::new();
+        let left = anchors.add(1, Gravity::Left);
+        let right = anchors.add(1, Gravity::Right);
+        let next_left = anchors.add(2, Gravity::Left);
+        let next_right = anchors.add(2, Gravity::Right);
+
+        edit_observed(&mut items, &mut anchors, |item| {
+            if item == 2 {
+                item.insert_after([4, 5]);
+            }
+        });
+
+        assert_eq!(items, vec![1, 2, 4, 5, 3]);
+        assert_eq!(anchors.get(left), Some(1));
+        assert_eq!(anchors.get(right), Some(1));
+        assert_eq!(anchors.get(next_left), Some(2));
+        assert_eq!(anchors.get(next_right), Some(4));
+    }
+
+    #[test]
+    fn replacing_an_anchored_item() {
+        let mut items = vec![1, 2, 3];
+
+        let mut anchors = Anchors::new();
+        let left = anchors.add(1, Gravity::Left);
+        let right = anchors.add(1, Gravity::Right);
+        let later = anchors.add(2, Gravity::Right);
+
+        edit_observed(&mut items, &mut anchors, |item| {
+            if item == 2 {
+                item.replace([4, 5, 6]);
+            }
+        });
+
+        assert_eq!(items, vec![1, 4, 5, 6, 3]);
+        assert_eq!(anchors.get(left), Some(1));
+        assert_eq!(anchors.get(right), Some(3));
+        assert_eq!(anchors.get(later), Some(4));
+    }
+
+    #[test]
+    fn replacing_an_anchored_item_with_nothing() {
+        let mut items = vec![1, 2, 3];
+
+        let mut anchors = Anchors::new();
+        let anchor = anchors.add(1, Gravity::Left);
+        let later = anchors.add(2, Gravity::Left);
+
+        edit_observed(&mut items, &mut anchors, |item| {
+            if item == 2 {
+                item.replace([]);
+            }
+        });
+
+        assert_eq!(items, vec![1, 3]);
+        assert!(anchors.is_deleted(anchor));
+        assert_eq!(anchors.get(later), Some(1));
+    }
+
+    #[test]
+    fn removing_items_around_an_anchor() {
+        let mut items = vec![1, 2, 3, 4, 5];
+
+        let mut anchors = Anchors::new();
+        let anchor = anchors.add(2, Gravity::Right);
+
+        edit_observed(&mut items, &mut anchors, |item| {
+            if item != 3 {
+                item.remove();
+            }
+        });
+
+        assert_eq!(items, vec![3]);
+        assert_eq!(anchors.get(anchor), Some(0));
+    }
+
+    #[test]
+    fn deleted_anchors_stay_deleted() {
+        let mut anchors = Anchors::new();
+        let anchor = anchors.add(0, Gravity::Right);
+
+        anchors.on_remove(0);
+        anchors.on_insert(0, 1);
+
+        assert!(anchors.is_deleted(anchor));
+    }
+
+    #[test]
+    fn following_moved_items() {
+        let mut items = vec![1, 2, 3, 4];
+
+        let mut anchors = Anchors::new();
+        let first = anchors.add(0, Gravity::Right);
+        let last = anchors.add(3, Gravity::Right);
+
+        reconcile_observed(
+            &mut items,
+            [4, 1, 2, 3],
+            |item| *item,
+            |_, _| {},
+            &mut anchors,
+        );
+
+        assert_eq!(items, vec![4, 1, 2, 3]);
+        assert_eq!(anchors.get(first), Some(1));
+        assert_eq!(anchors.get(last), Some(0));
+    }
+}
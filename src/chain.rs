@@ -0,0 +1,452 @@
+//! Treating several lists as one, so they can be edited in a single pass.
+//!
+//! [`Chain`] joins two lists, possibly of different types, and [`Segments`] joins a slice of
+//! lists of the same type. Both implement [`List`], mapping each index in the joined list to the
+//! segment holding it.
+
+use crate::List;
+use core::{
+    cell::Cell,
+    ops::{Deref, DerefMut},
+};
+
+/// Which segment receives items inserted at the boundary between two segments, where the end of
+/// one segment meets the start of the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Boundary {
+    /// Append the items to the earlier segment. This is the default.
+    Earlier,
+
+    /// Prepend the items to the later segment.
+    Later,
+}
+
+impl Default for Boundary {
+    fn default() -> Boundary {
+        Boundary::Earlier
+    }
+}
+
+/// Two lists of the same items, joined end to end.
+///
+/// ```
+/// use editer::{chain::Chain, edit};
+/// use std::collections::VecDeque;
+///
+/// let mut hot = VecDeque::from([1, 2]);
+/// let mut cold = vec![3, 4, 5];
+///
+/// edit(&mut Chain::new(&mut hot, &mut cold), |item| {
+///     if *item % 2 == 0 {
+///         item.remove();
+///     }
+/// });
+///
+/// assert_eq!(hot, VecDeque::from([1]));
+/// assert_eq!(cold, vec![3, 5]);
+/// ```
+#[derive(Debug)]
+pub struct Chain<'a, First, Second>
+where
+    First: List + ?Sized,
+    Second: List<Item = First::Item> + ?Sized,
+{
+    first: &'a mut First,
+    second: &'a mut Second,
+    boundary: Boundary,
+}
+
+impl<'a, First, Second> Chain<'a, First, Second>
+where
+    First: List + ?Sized,
+    Second: List<Item = First::Item> + ?Sized,
+{
+    /// Joins `first` and `second`, with items inserted at the boundary between them appended to
+    /// `first`.
+    pub fn new(first: &'a mut First, second: &'a mut Second) -> Chain<'a, First, Second> {
+        Chain {
+            first,
+            second,
+            boundary: Boundary::default(),
+        }
+    }
+
+    /// Sets which list receives items inserted at the boundary between them.
+    ///
+    /// ```
+    /// use editer::{chain::{Boundary, Chain}, edit};
+    ///
+    /// let mut first = vec![1];
+    /// let mut second = vec![2];
+    ///
+    /// edit(&mut Chain::new(&mut first, &mut second).boundary(Boundary::Later), |item| {
+    ///     if item == 1 {
+    ///         item.insert_after([3]);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(first, vec![1]);
+    /// assert_eq!(second, vec![3, 2]);
+    /// ```
+    pub fn boundary(mut self, boundary: Boundary) -> Chain<'a, First, Second> {
+        self.boundary = boundary;
+        self
+    }
+}
+
+impl<'a, First, Second> List for Chain<'a, First, Second>
+where
+    First: List + ?Sized,
+    Second: List<Item = First::Item> + ?Sized,
+{
+    type Item = First::Item;
+
+    fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+
+    fn index(&self, index: usize) -> &Self::Item {
+        match index.checked_sub(self.first.len()) {
+            None => self.first.index(index),
+            Some(index) => self.second.index(index),
+        }
+    }
+
+    fn index_mut(&mut self, index: usize) -> &mut Self::Item {
+        match index.checked_sub(self.first.len()) {
+            None => self.first.index_mut(index),
+            Some(index) => self.second.index_mut(index),
+        }
+    }
+
+    fn insert(&mut self, index: usize, items: impl ExactSizeIterator<Item = Self::Item>) {
+        let len = self.first.len();
+
+        if index < len || (index == len && self.boundary == Boundary::Earlier) {
+            self.first.insert(index, items);
+        } else {
+            self.second.insert(index - len, items);
+        }
+    }
+
+    fn remove(&mut self, index: usize) -> Self::Item {
+        match index.checked_sub(self.first.len()) {
+            None => self.first.remove(index),
+            Some(index) => self.second.remove(index),
+        }
+    }
+
+    fn replace(&mut self, index: usize, items: impl ExactSizeIterator<Item = Self::Item>) {
+        match index.checked_sub(self.first.len()) {
+            None => self.first.replace(index, items),
+            Some(index) => self.second.replace(index, items),
+        }
+    }
+}
+
+/// A slice of lists of the same type, joined end to end.
+///
+/// ```
+/// use editer::{chain::Segments, edit};
+///
+/// let mut pages = [vec!["a", "b"], vec![], vec!["c"]];
+///
+/// edit(&mut Segments::new(&mut pages), |item| {
+///     if item == "b" {
+///         item.replace(["b1", "b2"]);
+///     } else if item == "c" {
+///         item.insert_before(["x"]);
+///     }
+/// });
+///
+/// assert_eq!(pages, [vec!["a", "b1", "b2", "x"], vec![], vec!["c"]]);
+/// ```
+///
+/// Finding the segment holding an index starts from the segment found by the previous lookup,
+/// so visiting the items in order, as [`edit`](crate::edit) does, takes amortized constant time
+/// per item. Looking up an index before the previous one scans from the first segment, which
+/// takes time proportional to the number of segments, as does the first call to
+/// [`len`](List::len) after the segments are borrowed mutably as a slice.
+#[derive(Debug)]
+pub struct Segments<'a, Segment>
+where
+    Segment: List,
+{
+    segments: &'a mut [Segment],
+    boundary: Boundary,
+    /// A segment and the index of its first item, from the last lookup.
+    cursor: Cell<(usize, usize)>,
+    /// The total number of items, if it's known.
+    len: Cell<Option<usize>>,
+}
+
+impl<'a, Segment> Segments<'a, Segment>
+where
+    Segment: List,
+{
+    /// Joins `segments`, with items inserted at the boundary between two segments appended to
+    /// the earlier one.
+    pub fn new(segments: &'a mut [Segment]) -> Segments<'a, Segment> {
+        Segments {
+            segments,
+            boundary: Boundary::default(),
+            cursor: Cell::new((0, 0)),
+            len: Cell::new(None),
+        }
+    }
+
+    /// Sets which segment receives items inserted at the boundary between two segments.
+    ///
+    /// Empty segments count as boundaries too: with [`Boundary::Later`], items inserted at an
+    /// empty segment's position go into the last segment that starts there.
+    pub fn boundary(mut self, boundary: Boundary) -> Segments<'a, Segment> {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Returns the segment holding the item at `index` and the index of the item within it.
+    ///
+    /// Panics if `index` is out of bounds.
+    fn locate(&self, index: usize) -> (usize, usize) {
+        let (first, mut start) = self.cursor_before(index);
+
+        for (segment, items) in self.segments.iter().enumerate().skip(first) {
+            if index - start < items.len() {
+                self.cursor.set((segment, start));
+                return (segment, index - start);
+            }
+
+            start += items.len();
+        }
+
+        panic!("index out of bounds");
+    }
+
+    /// Returns the segment to insert items at `index` into and the index within it.
+    ///
+    /// Panics if `index` is out of bounds or there are no segments.
+    fn locate_insertion(&self, index: usize) -> (usize, usize) {
+        let (first, mut start) = self.cursor_before(index);
+        let mut found = None;
+
+        for (segment, items) in self.segments.iter().enumerate().skip(first) {
+            if index - start <= items.len() {
+                found = Some((segment, start));
+
+                if index - start < items.len() || self.boundary == Boundary::Earlier {
+                    break;
+                }
+            }
+
+            start += items.len();
+        }
+
+        let (segment, start) = found.expect("insertion index out of bounds");
+        self.cursor.set((segment, start));
+        (segment, index - start)
+    }
+
+    /// Updates the cached length for `inserted` items being added and `removed` items being
+    /// removed.
+    fn resize(&self, inserted: usize, removed: usize) {
+        self.len
+            .set(self.len.get().map(|len| len + inserted - removed));
+    }
+
+    /// Returns the segment to start looking for `index` from and the index of its first item:
+    /// the segment from the last lookup if it starts before `index`, or else the first segment.
+    ///
+    /// Inserting into or removing from a segment doesn't move its first item, so the cursor only
+    /// needs resetting when the segments are borrowed mutably as a slice.
+    fn cursor_before(&self, index: usize) -> (usize, usize) {
+        let (segment, start) = self.cursor.get();
+
+        if start < index {
+            (segment, start)
+        } else {
+            (0, 0)
+        }
+    }
+}
+
+impl<'a, Segment> Deref for Segments<'a, Segment>
+where
+    Segment: List,
+{
+    type Target = [Segment];
+
+    fn deref(&self) -> &[Segment] {
+        self.segments
+    }
+}
+
+impl<'a, Segment> DerefMut for Segments<'a, Segment>
+where
+    Segment: List,
+{
+    fn deref_mut(&mut self) -> &mut [Segment] {
+        self.cursor.set((0, 0));
+        self.len.set(None);
+        self.segments
+    }
+}
+
+impl<'a, Segment> List for Segments<'a, Segment>
+where
+    Segment: List,
+{
+    type Item = Segment::Item;
+
+    fn len(&self) -> usize {
+        let len = self
+            .len
+            .get()
+            .unwrap_or_else(|| self.segments.iter().map(List::len).sum());
+
+        self.len.set(Some(len));
+        len
+    }
+
+    fn index(&self, index: usize) -> &Self::Item {
+        let (segment, index) = self.locate(index);
+        self.segments[segment].index(index)
+    }
+
+    fn index_mut(&mut self, index: usize) -> &mut Self::Item {
+        let (segment, index) = self.locate(index);
+        self.segments[segment].index_mut(index)
+    }
+
+    fn insert(&mut self, index: usize, items: impl ExactSizeIterator<Item = Self::Item>) {
+        let (segment, index) = self.locate_insertion(index);
+        let count = items.len();
+        self.segments[segment].insert(index, items);
+        self.resize(count, 0);
+    }
+
+    fn remove(&mut self, index: usize) -> Self::Item {
+        let (segment, index) = self.locate(index);
+        let item = self.segments[segment].remove(index);
+        self.resize(0, 1);
+        item
+    }
+
+    fn replace(&mut self, index: usize, items: impl ExactSizeIterator<Item = Self::Item>) {
+        let (segment, index) = self.locate(index);
+        let count = items.len();
+        self.segments[segment].replace(index, items);
+        self.resize(count, 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Boundary, Chain, Segments};
+    use crate::{edit, List};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn visiting_every_item_in_a_chain() {
+        let mut first = VecDeque::from([1, 2]);
+        let mut second = vec![3];
+        let mut visited = Vec::new();
+
+        edit(&mut Chain::new(&mut first, &mut second), |item| {
+            visited.push(*item)
+        });
+
+        assert_eq!(visited, [1, 2, 3]);
+    }
+
+    #[test]
+    fn chaining_chains() {
+        let mut a = vec![1];
+        let mut b = vec![2];
+        let mut c = vec![3];
+        let mut ab = Chain::new(&mut a, &mut b);
+
+        edit(&mut Chain::new(&mut ab, &mut c), |mut item| *item *= 10);
+
+        assert_eq!((a, b, c), (vec![10], vec![20], vec![30]));
+    }
+
+    #[test]
+    fn inserting_at_the_boundary_of_a_chain() {
+        let mut first = vec![1];
+        let mut second = vec![2];
+
+        let mut chain = Chain::new(&mut first, &mut second);
+        chain.insert(1, [3].into_iter());
+        let mut chain = chain.boundary(Boundary::Later);
+        chain.insert(2, [4].into_iter());
+
+        assert_eq!(first, vec![1, 3]);
+        assert_eq!(second, vec![4, 2]);
+    }
+
+    #[test]
+    fn moving_items_between_segments() {
+        let mut pages = [vec![1, 2], vec![3, 4]];
+
+        edit(&mut Segments::new(&mut pages), |item| {
+            if item == 1 {
+                item.move_to_end();
+            }
+        });
+
+        assert_eq!(pages, [vec![2], vec![3, 4, 1]]);
+    }
+
+    #[test]
+    fn inserting_at_the_boundary_of_segments() {
+        let mut pages = [vec![1], vec![], vec![], vec![2]];
+
+        let mut segments = Segments::new(&mut pages);
+        segments.insert(1, [3].into_iter());
+        let mut segments = segments.boundary(Boundary::Later);
+        segments.insert(2, [4].into_iter());
+        segments.insert(0, [5].into_iter());
+
+        assert_eq!(pages, [vec![5, 1, 3], vec![], vec![], vec![4, 2]]);
+    }
+
+    #[test]
+    fn inserting_into_empty_segments() {
+        let mut pages: [Vec<i32>; 2] = [vec![], vec![]];
+
+        Segments::new(&mut pages).insert(0, [1].into_iter());
+        Segments::new(&mut pages)
+            .boundary(Boundary::Later)
+            .insert(1, [2].into_iter());
+
+        assert_eq!(pages, [vec![1], vec![2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index out of bounds")]
+    fn inserting_into_no_segments() {
+        let mut pages: [Vec<i32>; 0] = [];
+
+        Segments::new(&mut pages).insert(0, [1].into_iter());
+    }
+
+    #[test]
+    fn looking_up_items_out_of_order() {
+        let mut pages = [vec![1, 2], vec![], vec![3], vec![4, 5]];
+        let mut segments = Segments::new(&mut pages);
+
+        assert_eq!(*segments.index(4), 5);
+        assert_eq!(*segments.index(0), 1);
+        assert_eq!(*segments.index(2), 3);
+
+        segments.insert(1, [6].into_iter());
+        assert_eq!(*segments.index(3), 3);
+
+        segments[0].push(7);
+        assert_eq!(segments.len(), 7);
+        assert_eq!(*segments.index(4), 3);
+        assert_eq!(segments.remove(6), 5);
+        assert_eq!(segments.len(), 6);
+        assert_eq!(pages, [vec![1, 6, 2, 7], vec![], vec![3], vec![4]]);
+    }
+}
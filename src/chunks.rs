@@ -0,0 +1,291 @@
+//! Editing a list a fixed number of items at a time.
+
+use crate::{range::RangeSlot, List};
+use core::{
+    cmp,
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
+
+/// Iterates over `items` in chunks of `size` consecutive items, calling `edit` with a
+/// [`ChunkSlot`] for each chunk. The `ChunkSlot` allows accessing the items in the chunk and/or
+/// updating the list at the chunk's position.
+///
+/// If the length of the list isn't a multiple of `size`, the last chunk is shorter than `size`.
+/// Items inserted by `edit` aren't included in any chunk.
+///
+/// Panics if `size` is zero.
+///
+/// ```
+/// use editer::chunks::edit_chunks;
+///
+/// let mut items = vec!["a", "1", "b", "2", "c"];
+///
+/// edit_chunks(&mut items, 2, |chunk| {
+///     if chunk.len() < 2 {
+///         chunk.remove();
+///     } else if chunk[0] == "b" {
+///         chunk.replace(["b", "20", "bb", "200"]);
+///     }
+/// });
+///
+/// assert_eq!(items, vec!["a", "1", "b", "20", "bb", "200"]);
+/// ```
+pub fn edit_chunks<List>(items: &mut List, size: usize, mut edit: impl FnMut(ChunkSlot<List>))
+where
+    List: self::List + ?Sized,
+{
+    assert!(size != 0, "chunk size must be non-zero");
+
+    let mut index = 0;
+
+    while index < items.len() {
+        let len = cmp::min(size, items.len() - index);
+        let mut stride = len;
+        edit(ChunkSlot::new(items, index, len, &mut stride));
+        index += stride;
+    }
+}
+
+/// Represents the current chunk of consecutive items in an [`edit_chunks`].
+///
+/// Dereferences to a [`RangeSlot`] for accessing the items in the chunk. Only the last chunk can
+/// be shorter than the chunk size.
+pub struct ChunkSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+{
+    range: RangeSlot<'list, List>,
+    stride: &'stride mut usize,
+}
+
+impl<'list, 'stride, List> ChunkSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+{
+    fn new(
+        list: &'list mut List,
+        index: usize,
+        len: usize,
+        stride: &'stride mut usize,
+    ) -> ChunkSlot<'list, 'stride, List> {
+        ChunkSlot {
+            range: RangeSlot::new(list, index, len),
+            stride,
+        }
+    }
+
+    /// Inserts zero or more `items` before the chunk.
+    ///
+    /// ```
+    /// # use editer::chunks::edit_chunks;
+    /// #
+    /// let mut items = vec![1, 2, 3, 4];
+    ///
+    /// edit_chunks(&mut items, 2, |chunk| {
+    ///     if chunk[0] == 3 {
+    ///         chunk.insert_before([0]);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(items, vec![1, 2, 0, 3, 4]);
+    /// ```
+    pub fn insert_before<Items>(self, items: Items)
+    where
+        Items: IntoIterator,
+        Items::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
+    {
+        let len = self.range.len();
+        *self.stride = self.range.insert(0, items.into_iter()) + len;
+    }
+
+    /// Inserts zero or more `items` after the chunk.
+    ///
+    /// ```
+    /// # use editer::chunks::edit_chunks;
+    /// #
+    /// let mut items = vec![1, 2, 3, 4];
+    ///
+    /// edit_chunks(&mut items, 2, |chunk| {
+    ///     if chunk[0] == 1 {
+    ///         chunk.insert_after([0]);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(items, vec![1, 2, 0, 3, 4]);
+    /// ```
+    pub fn insert_after<Items>(self, items: Items)
+    where
+        Items: IntoIterator,
+        Items::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
+    {
+        let len = self.range.len();
+        *self.stride = self.range.insert(len, items.into_iter()) + len;
+    }
+
+    /// Replaces the items in the chunk with zero or more `items`.
+    ///
+    /// ```
+    /// # use editer::chunks::edit_chunks;
+    /// #
+    /// let mut items = vec![1, 2, 3, 4, 5, 6];
+    ///
+    /// edit_chunks(&mut items, 3, |chunk| {
+    ///     let sum = chunk.iter().sum::<i32>();
+    ///     chunk.replace([sum]);
+    /// });
+    ///
+    /// assert_eq!(items, vec![6, 15]);
+    /// ```
+    pub fn replace<Items>(self, items: Items)
+    where
+        Items: IntoIterator,
+        Items::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
+    {
+        *self.stride = self.range.replace(items.into_iter());
+    }
+
+    /// Removes the items in the chunk.
+    ///
+    /// ```
+    /// # use editer::chunks::edit_chunks;
+    /// #
+    /// let mut items = vec![1, 2, 3, 4, 5, 6];
+    ///
+    /// edit_chunks(&mut items, 2, |chunk| {
+    ///     if chunk[0] == 3 {
+    ///         chunk.remove();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(items, vec![1, 2, 5, 6]);
+    /// ```
+    pub fn remove(self) {
+        *self.stride = 0;
+        self.range.remove();
+    }
+}
+
+impl<'list, 'stride, List> Deref for ChunkSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+{
+    type Target = RangeSlot<'list, List>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.range
+    }
+}
+
+impl<'list, 'stride, List> DerefMut for ChunkSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.range
+    }
+}
+
+impl<'list, 'stride, List, Item> PartialEq<[Item]> for ChunkSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: PartialEq<Item>,
+{
+    fn eq(&self, other: &[Item]) -> bool {
+        self.range.eq(other)
+    }
+}
+
+impl<'list, 'stride, List, Item, const N: usize> PartialEq<[Item; N]>
+    for ChunkSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: PartialEq<Item>,
+{
+    fn eq(&self, other: &[Item; N]) -> bool {
+        self.range.eq(other)
+    }
+}
+
+impl<'list, 'stride, List> Debug for ChunkSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: Debug,
+{
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.range.fmt(formatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edit_chunks;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn visiting_every_chunk() {
+        let mut items = vec![1, 2, 3, 4, 5, 6, 7];
+        let mut chunks = Vec::new();
+
+        edit_chunks(&mut items, 3, |chunk| {
+            chunks.push(chunk.iter().copied().collect::<Vec<_>>())
+        });
+
+        assert_eq!(chunks, [vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+    }
+
+    #[test]
+    fn mutating_items_in_a_chunk() {
+        let mut items = VecDeque::from([(1, 2), (3, 4)]);
+
+        edit_chunks(&mut items, 2, |mut chunk| {
+            let first = chunk[0];
+            chunk[0] = chunk[1];
+            chunk[1] = first;
+        });
+
+        assert_eq!(items, VecDeque::from([(3, 4), (1, 2)]));
+    }
+
+    #[test]
+    fn replacing_chunks_with_more_and_fewer_items() {
+        let mut items = VecDeque::from([1, 2, 3, 4, 5, 6, 7]);
+
+        edit_chunks(&mut items, 2, |chunk| {
+            if chunk == [1, 2] {
+                chunk.replace([8, 9, 10]);
+            } else if chunk == [3, 4] {
+                chunk.replace([11]);
+            } else if chunk == [7] {
+                chunk.replace([12, 13]);
+            }
+        });
+
+        assert_eq!(items, VecDeque::from([8, 9, 10, 11, 5, 6, 12, 13]));
+    }
+
+    #[test]
+    fn not_chunking_inserted_items() {
+        let mut items = vec![1, 2, 3, 4];
+        let mut chunks = Vec::new();
+
+        edit_chunks(&mut items, 2, |chunk| {
+            chunks.push(format!("{:?}", chunk));
+
+            if chunk[0] == 1 {
+                chunk.insert_after([5]);
+            } else {
+                chunk.insert_before([6, 7, 8]);
+            }
+        });
+
+        assert_eq!(items, vec![1, 2, 5, 6, 7, 8, 3, 4]);
+        assert_eq!(chunks, ["[1, 2]", "[3, 4]"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunking_by_zero() {
+        edit_chunks(&mut vec![1], 0, |_| {});
+    }
+}
@@ -0,0 +1,378 @@
+//! Composing editor functions into pipelines that edit a list in a single pass.
+//!
+//! ```
+//! use editer::{
+//!     editor::{edit_with, Editor},
+//!     slot::Slot,
+//! };
+//!
+//! let trim = |mut line: Slot<Vec<String>>| *line = line.trim().to_string();
+//!
+//! let expand = |line: Slot<Vec<String>>| {
+//!     if *line == "greetings" {
+//!         line.replace([String::from("hello"), String::from(""), String::from("hi")]);
+//!     }
+//! };
+//!
+//! let mut lines: Vec<String> = vec![" greetings ", "", " bye"]
+//!     .into_iter()
+//!     .map(String::from)
+//!     .collect();
+//!
+//! edit_with(&mut lines, trim.then(expand).filter(|line| !line.is_empty()));
+//!
+//! assert_eq!(lines, vec!["hello", "hi", "bye"]);
+//! ```
+
+use crate::{observer::EditObserver, slot::Slot, Edit};
+
+/// Iterates over `items`, calling `editor` with a [`Slot`] for each item.
+///
+/// This is [`edit`](crate::edit) for [`Editor`]s that aren't closures, such as pipelines built
+/// with [`Editor::then`].
+pub fn edit_with<List>(items: &mut List, mut editor: impl Editor<List>)
+where
+    List: crate::List + ?Sized,
+{
+    items.edit(|slot| editor.edit(slot))
+}
+
+/// Something that edits a list one [`Slot`] at a time, like the function passed to
+/// [`edit`](crate::edit).
+///
+/// `Editor` is implemented for every function that takes a `Slot`. Its combinators build larger
+/// editors out of smaller ones, all of which are applied in a single pass over the list.
+pub trait Editor<List, Observer = ()>
+where
+    List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
+{
+    /// Edits the list at the position of `slot`.
+    fn edit(&mut self, slot: Slot<List, Observer>);
+
+    /// Returns an editor that applies `self`, then applies `next` to each item that `self`
+    /// leaves in place of the current item.
+    ///
+    /// `next` is applied to items inserted or used as replacements by `self`, as well as to the
+    /// current item if `self` keeps it. It isn't applied to items that `self` moves away.
+    ///
+    /// ```
+    /// use editer::{
+    ///     editor::{edit_with, Editor},
+    ///     slot::Slot,
+    /// };
+    ///
+    /// let mut items = vec![1, 2, 3];
+    ///
+    /// let duplicate = |item: Slot<Vec<i32>>| {
+    ///     let copy = *item;
+    ///     item.insert_after([copy]);
+    /// };
+    ///
+    /// let scale = |mut item: Slot<Vec<i32>>| *item *= 10;
+    ///
+    /// edit_with(&mut items, duplicate.then(scale));
+    ///
+    /// assert_eq!(items, vec![10, 10, 20, 20, 30, 30]);
+    /// ```
+    fn then<Next>(self, next: Next) -> Then<Self, Next>
+    where
+        Self: Sized,
+        Next: Editor<List, Observer>,
+    {
+        Then { first: self, next }
+    }
+
+    /// Returns an editor that applies `self` only to items for which `predicate` returns `true`,
+    /// and leaves other items alone.
+    ///
+    /// ```
+    /// use editer::{
+    ///     editor::{edit_with, Editor},
+    ///     slot::Slot,
+    /// };
+    ///
+    /// let mut items = vec![1, 2, 3, 4];
+    ///
+    /// let negate = |mut item: Slot<Vec<i32>>| *item = -*item;
+    ///
+    /// edit_with(&mut items, negate.when(|item| item % 2 == 0));
+    ///
+    /// assert_eq!(items, vec![1, -2, 3, -4]);
+    /// ```
+    fn when<Predicate>(self, predicate: Predicate) -> When<Self, Predicate>
+    where
+        Self: Sized,
+        Predicate: FnMut(&List::Item) -> bool,
+    {
+        When {
+            editor: self,
+            predicate,
+        }
+    }
+
+    /// Returns an editor that applies `self`, then removes each item that `self` leaves in place
+    /// of the current item for which `predicate` returns `false`.
+    ///
+    /// ```
+    /// use editer::{
+    ///     editor::{edit_with, Editor},
+    ///     slot::Slot,
+    /// };
+    ///
+    /// let mut items = vec![1, 2, 3, 4];
+    ///
+    /// let decrement = |mut item: Slot<Vec<i32>>| *item -= 1;
+    ///
+    /// edit_with(&mut items, decrement.filter(|item| item % 2 == 0));
+    ///
+    /// assert_eq!(items, vec![0, 2]);
+    /// ```
+    fn filter<Predicate>(self, predicate: Predicate) -> Filter<Self, Predicate>
+    where
+        Self: Sized,
+        Predicate: FnMut(&List::Item) -> bool,
+    {
+        Filter {
+            editor: self,
+            predicate,
+        }
+    }
+}
+
+impl<List, Observer, Function> Editor<List, Observer> for Function
+where
+    List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
+    Function: FnMut(Slot<List, Observer>),
+{
+    fn edit(&mut self, slot: Slot<List, Observer>) {
+        self(slot)
+    }
+}
+
+/// An editor that applies one editor, then another to its results. See [`Editor::then`].
+#[derive(Clone, Debug)]
+pub struct Then<First, Next> {
+    first: First,
+    next: Next,
+}
+
+impl<List, Observer, First, Next> Editor<List, Observer> for Then<First, Next>
+where
+    List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
+    First: Editor<List, Observer>,
+    Next: Editor<List, Observer>,
+{
+    fn edit(&mut self, slot: Slot<List, Observer>) {
+        let Then { first, next } = self;
+        chain(slot, |slot| first.edit(slot), |slot| next.edit(slot));
+    }
+}
+
+/// An editor that only applies to some items. See [`Editor::when`].
+#[derive(Clone, Debug)]
+pub struct When<Editor, Predicate> {
+    editor: Editor,
+    predicate: Predicate,
+}
+
+impl<List, Observer, Inner, Predicate> Editor<List, Observer> for When<Inner, Predicate>
+where
+    List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
+    Inner: Editor<List, Observer>,
+    Predicate: FnMut(&List::Item) -> bool,
+{
+    fn edit(&mut self, slot: Slot<List, Observer>) {
+        if (self.predicate)(slot.get()) {
+            self.editor.edit(slot);
+        }
+    }
+}
+
+/// An editor that removes some of the results of another editor. See [`Editor::filter`].
+#[derive(Clone, Debug)]
+pub struct Filter<Editor, Predicate> {
+    editor: Editor,
+    predicate: Predicate,
+}
+
+impl<List, Observer, Inner, Predicate> Editor<List, Observer> for Filter<Inner, Predicate>
+where
+    List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
+    Inner: Editor<List, Observer>,
+    Predicate: FnMut(&List::Item) -> bool,
+{
+    fn edit(&mut self, slot: Slot<List, Observer>) {
+        let Filter { editor, predicate } = self;
+
+        chain(
+            slot,
+            |slot| editor.edit(slot),
+            |slot| {
+                if !predicate(slot.get()) {
+                    slot.remove();
+                }
+            },
+        );
+    }
+}
+
+/// Applies `first` to `slot`, then applies `next` to each item that `first` left in its place.
+fn chain<List, Observer>(
+    slot: Slot<List, Observer>,
+    first: impl FnOnce(Slot<List, Observer>),
+    mut next: impl FnMut(Slot<List, Observer>),
+) where
+    List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
+{
+    let (list, index, stride, observer) = slot.into_parts();
+
+    first(Slot::new(list, index, stride, observer));
+
+    // The items `first` left in place of the current item follow any items that shifted into
+    // place before them.
+    let (mut remaining, mut shift) = stride.take_span();
+    let mut position = index + shift;
+    let mut span = 0;
+
+    while remaining > 0 {
+        // An item moved forward by `next` may have landed among the items yet to be passed to
+        // it, and shouldn't be passed to it again.
+        if stride.skip(list.len() - position) {
+            position += 1;
+            span += 1;
+            continue;
+        }
+
+        next(Slot::new(list, position, stride, observer));
+
+        let (value, shifted) = stride.take_span();
+        position += value + shifted;
+        span += value;
+        shift += shifted;
+        remaining -= 1;
+    }
+
+    stride.restore_span(span, shift);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_with, Editor};
+    use crate::{edit_with_summary, slot::Slot};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn chaining_editors_in_a_single_pass() {
+        let mut items = VecDeque::from([1, 2, 3]);
+        let mut log = Vec::new();
+
+        let first = |item: Slot<VecDeque<i32>>| {
+            log.push(format!("first {}", item));
+        };
+
+        let mut second_log = Vec::new();
+
+        let second = |item: Slot<VecDeque<i32>>| {
+            second_log.push(format!("second {}", item));
+        };
+
+        edit_with(&mut items, first.then(second));
+
+        assert_eq!(log, ["first 1", "first 2", "first 3"]);
+        assert_eq!(second_log, ["second 1", "second 2", "second 3"]);
+    }
+
+    #[test]
+    fn feeding_replacements_to_the_next_editor() {
+        let mut items = vec![1, 2, 3];
+
+        let expand = |item: Slot<Vec<i32>>| {
+            if item == 2 {
+                item.replace([4, 5, 6]);
+            }
+        };
+
+        let drop_odd = |item: Slot<Vec<i32>>| {
+            if *item % 2 == 1 {
+                item.remove();
+            }
+        };
+
+        let double = |mut item: Slot<Vec<i32>>| *item *= 2;
+
+        let summary = edit_with_summary(&mut items, |slot| {
+            expand.then(drop_odd).then(double).edit(slot)
+        });
+
+        assert_eq!(items, vec![8, 12]);
+        assert_eq!(summary.visited, 3);
+    }
+
+    #[test]
+    fn not_passing_moved_items_to_the_next_editor() {
+        let mut items = vec![1, 2, 3, 4];
+        let mut seen = Vec::new();
+
+        let first = |item: Slot<Vec<i32>>| {
+            if item == 2 {
+                item.move_to_end();
+            } else if item == 3 {
+                item.move_to_front();
+            }
+        };
+
+        edit_with(
+            &mut items,
+            first.then(|item: Slot<Vec<i32>>| seen.push(*item)),
+        );
+
+        assert_eq!(items, vec![3, 1, 4, 2]);
+        assert_eq!(seen, [1, 4]);
+    }
+
+    #[test]
+    fn moving_items_from_the_next_editor() {
+        let mut items = vec![1, 2, 3];
+        let mut seen = Vec::new();
+
+        let expand = |item: Slot<Vec<i32>>| {
+            if item == 1 {
+                item.replace([4, 5, 6]);
+            }
+        };
+
+        let shuffle = |item: Slot<Vec<i32>>| {
+            seen.push(*item);
+
+            if item == 4 {
+                item.move_by(1);
+            } else if item == 5 {
+                item.move_to_front();
+            } else if item == 2 {
+                item.move_by(1);
+            }
+        };
+
+        edit_with(&mut items, expand.then(shuffle));
+
+        assert_eq!(items, vec![5, 4, 6, 3, 2]);
+        assert_eq!(seen, [4, 5, 6, 2, 3]);
+    }
+
+    #[test]
+    fn editing_some_items() {
+        let mut items = vec![1, 2, 3, 4];
+
+        let remove = |item: Slot<Vec<i32>>| item.remove();
+
+        edit_with(&mut items, remove.when(|item| *item > 2));
+
+        assert_eq!(items, vec![1, 2]);
+    }
+}
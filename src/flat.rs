@@ -0,0 +1,274 @@
+//! Editing the items of nested lists, with access to the lists that contain them.
+
+use crate::{slot::Slot, List, Stride};
+use core::{
+    convert::Infallible,
+    fmt::{Debug, Display},
+    ops::{Deref, DerefMut},
+};
+
+/// Iterates over the items of each list in `items`, a list of lists, calling `edit` with a
+/// [`FlatSlot`] for each inner item. The `FlatSlot` allows accessing the inner item and updating
+/// either the inner list at the current position or the outer list at the current inner list.
+///
+/// ```
+/// use editer::flat::edit_flat;
+///
+/// let mut paragraphs = vec![vec!["a", "b"], vec!["---"], vec!["c"]];
+///
+/// edit_flat(&mut paragraphs, |word| {
+///     if word == "---" {
+///         word.outer().replace([vec!["d"], vec!["e"]]);
+///     } else if word == "b" {
+///         word.inner().insert_after(["b2"]);
+///     }
+/// });
+///
+/// assert_eq!(paragraphs, vec![vec!["a", "b", "b2"], vec!["d"], vec!["e"], vec!["c"]]);
+/// ```
+pub fn edit_flat<List>(items: &mut List, mut edit: impl FnMut(FlatSlot<List>))
+where
+    List: self::List + ?Sized,
+    List::Item: self::List,
+{
+    let outer = crate::drive(
+        items,
+        &mut Stride::new(),
+        |items| items.len(),
+        |items, outer_index, outer_stride| {
+            let mut done = false;
+
+            // Stops visiting the inner list once the `FlatSlot` has been converted into a slot in
+            // the outer list, which is left to advance the outer stride instead.
+            let _ = crate::drive(
+                items,
+                &mut Stride::new(),
+                |items| items.index(outer_index).len(),
+                |items, inner_index, inner_stride| {
+                    edit(FlatSlot {
+                        list: items,
+                        outer_index,
+                        outer_stride,
+                        inner_index,
+                        inner_stride,
+                        done: &mut done,
+                        observer: &mut (),
+                    });
+
+                    if done {
+                        Err(())
+                    } else {
+                        Ok(())
+                    }
+                },
+            );
+
+            Ok::<(), Infallible>(())
+        },
+    );
+
+    match outer {
+        Ok(()) => {}
+        Err(never) => match never {},
+    }
+}
+
+/// Represents the current position in an [`edit_flat`]: an item of an inner list, within the
+/// outer list.
+///
+/// Use [`inner`](FlatSlot::inner) to update the inner list at the current item, or
+/// [`outer`](FlatSlot::outer) to update the outer list at the current inner list.
+pub struct FlatSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: self::List,
+{
+    list: &'list mut List,
+    outer_index: usize,
+    outer_stride: &'stride mut Stride,
+    inner_index: usize,
+    inner_stride: &'stride mut Stride,
+    done: &'stride mut bool,
+    observer: &'stride mut (),
+}
+
+type InnerItem<List> = <<List as self::List>::Item as self::List>::Item;
+
+impl<'list, 'stride, List> FlatSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: self::List,
+{
+    /// Returns a shared reference to the current inner item.
+    pub fn get(&self) -> &InnerItem<List> {
+        self.list.index(self.outer_index).index(self.inner_index)
+    }
+
+    /// Returns a mutable reference to the current inner item.
+    pub fn get_mut(&mut self) -> &mut InnerItem<List> {
+        self.list
+            .index_mut(self.outer_index)
+            .index_mut(self.inner_index)
+    }
+
+    /// Returns a shared reference to the inner list containing the current item.
+    pub fn list(&self) -> &List::Item {
+        self.list.index(self.outer_index)
+    }
+
+    /// Converts the `FlatSlot` into a [`Slot`] for the current item in its inner list.
+    pub fn inner(self) -> Slot<'list, 'stride, List::Item> {
+        Slot::new(
+            self.list.index_mut(self.outer_index),
+            self.inner_index,
+            self.inner_stride,
+            self.observer,
+        )
+    }
+
+    /// Converts the `FlatSlot` into a [`Slot`] for the inner list containing the current item,
+    /// in the outer list.
+    ///
+    /// The rest of the inner list isn't visited, even if the `Slot` is dropped without calling
+    /// any of its methods.
+    ///
+    /// ```
+    /// # use editer::flat::edit_flat;
+    /// #
+    /// let mut rows = vec![vec![1, 2], vec![3, 0, 4], vec![5]];
+    ///
+    /// edit_flat(&mut rows, |cell| {
+    ///     if cell == 0 {
+    ///         cell.outer().remove();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(rows, vec![vec![1, 2], vec![5]]);
+    /// ```
+    pub fn outer(self) -> Slot<'list, 'stride, List> {
+        *self.done = true;
+        Slot::new(
+            self.list,
+            self.outer_index,
+            self.outer_stride,
+            self.observer,
+        )
+    }
+}
+
+impl<'list, 'stride, List> Deref for FlatSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: self::List,
+{
+    type Target = InnerItem<List>;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<'list, 'stride, List> DerefMut for FlatSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: self::List,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.get_mut()
+    }
+}
+
+impl<'list, 'stride, List> PartialEq<InnerItem<List>> for FlatSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: self::List,
+    InnerItem<List>: PartialEq,
+{
+    fn eq(&self, other: &InnerItem<List>) -> bool {
+        self.get().eq(other)
+    }
+}
+
+impl<'list, 'stride, List> Display for FlatSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: self::List,
+    InnerItem<List>: Display,
+{
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.get().fmt(formatter)
+    }
+}
+
+impl<'list, 'stride, List> Debug for FlatSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: self::List,
+    InnerItem<List>: Debug,
+{
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.debug_tuple("FlatSlot").field(self.get()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edit_flat;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn visiting_every_inner_item() {
+        let mut rows = vec![VecDeque::from([1, 2]), VecDeque::new(), VecDeque::from([3])];
+        let mut visited = Vec::new();
+
+        edit_flat(&mut rows, |cell| visited.push(*cell));
+
+        assert_eq!(visited, [1, 2, 3]);
+    }
+
+    #[test]
+    fn editing_inner_lists() {
+        let mut rows = vec![vec![1, 2, 3], vec![4, 5]];
+
+        edit_flat(&mut rows, |mut cell| {
+            if cell == 2 {
+                cell.inner().remove();
+            } else if cell == 4 {
+                cell.inner().replace([6, 7]);
+            } else {
+                *cell *= 10;
+            }
+        });
+
+        assert_eq!(rows, vec![vec![10, 30], vec![6, 7, 50]]);
+    }
+
+    #[test]
+    fn editing_the_outer_list() {
+        let mut rows = vec![vec![1, 2], vec![3, 4], vec![5]];
+        let mut visited = Vec::new();
+
+        edit_flat(&mut rows, |cell| {
+            visited.push(*cell);
+
+            if cell == 1 {
+                cell.outer().insert_after([vec![9]]);
+            } else if cell == 3 {
+                cell.outer().move_to_end();
+            }
+        });
+
+        assert_eq!(visited, [1, 3, 5]);
+        assert_eq!(rows, vec![vec![1, 2], vec![9], vec![5], vec![3, 4]]);
+    }
+
+    #[test]
+    fn reading_the_inner_list() {
+        let mut rows = vec![vec![1, 2], vec![3]];
+        let mut lengths = Vec::new();
+
+        edit_flat(&mut rows, |cell| lengths.push(cell.list().len()));
+
+        assert_eq!(lengths, [2, 2, 1]);
+    }
+}
@@ -0,0 +1,274 @@
+//! Editing runs of consecutive items with equal keys.
+
+use crate::{range::RangeSlot, List};
+use core::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
+
+/// Iterates over the maximal runs of consecutive items in `items` for which `key` returns equal
+/// keys, calling `edit` with a [`GroupSlot`] for each run. The `GroupSlot` allows accessing the
+/// items in the group and/or replacing or merging them.
+///
+/// Items that replace a group aren't grouped again, and aren't grouped with the items after them.
+///
+/// ```
+/// use editer::groups::edit_groups_by_key;
+///
+/// let mut lines = vec![(1, "GET /"), (1, "200"), (2, "GET /a"), (1, "GET /b"), (1, "404")];
+///
+/// edit_groups_by_key(
+///     &mut lines,
+///     |line| line.0,
+///     |group| group.merge(|first, line| first.1 = if line.1 == "404" { "missing" } else { "ok" }),
+/// );
+///
+/// assert_eq!(lines, vec![(1, "ok"), (2, "GET /a"), (1, "missing")]);
+/// ```
+pub fn edit_groups_by_key<List, Key>(
+    items: &mut List,
+    mut key: impl FnMut(&List::Item) -> Key,
+    mut edit: impl FnMut(GroupSlot<List, Key>),
+) where
+    List: self::List + ?Sized,
+    Key: PartialEq,
+{
+    let mut index = 0;
+
+    while index < items.len() {
+        let group_key = key(items.index(index));
+        let mut len = 1;
+
+        while index + len < items.len() && key(items.index(index + len)) == group_key {
+            len += 1;
+        }
+
+        let mut stride = len;
+        edit(GroupSlot::new(items, index, len, group_key, &mut stride));
+        index += stride;
+    }
+}
+
+/// Represents the current run of consecutive items with equal keys in an
+/// [`edit_groups_by_key`].
+///
+/// Dereferences to a [`RangeSlot`] for accessing the items in the group, of which there's at least
+/// one. If the `GroupSlot` is dropped without calling any of its methods that take `self`, the
+/// items in the group are left alone.
+pub struct GroupSlot<'list, 'stride, List, Key>
+where
+    List: self::List + ?Sized,
+{
+    range: RangeSlot<'list, List>,
+    key: Key,
+    stride: &'stride mut usize,
+}
+
+impl<'list, 'stride, List, Key> GroupSlot<'list, 'stride, List, Key>
+where
+    List: self::List + ?Sized,
+{
+    fn new(
+        list: &'list mut List,
+        index: usize,
+        len: usize,
+        key: Key,
+        stride: &'stride mut usize,
+    ) -> GroupSlot<'list, 'stride, List, Key> {
+        GroupSlot {
+            range: RangeSlot::new(list, index, len),
+            key,
+            stride,
+        }
+    }
+
+    /// Returns the key shared by the items in the group.
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Replaces the items in the group with zero or more `items`.
+    ///
+    /// ```
+    /// # use editer::groups::edit_groups_by_key;
+    /// #
+    /// let mut items = vec![1, 1, 2, 1, 1, 1];
+    ///
+    /// edit_groups_by_key(&mut items, |item| *item, |group| {
+    ///     let len = group.len();
+    ///     let item = group[0];
+    ///     group.replace([item, len]);
+    /// });
+    ///
+    /// assert_eq!(items, vec![1, 2, 2, 1, 1, 3]);
+    /// ```
+    pub fn replace<Items>(self, items: Items)
+    where
+        Items: IntoIterator,
+        Items::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
+    {
+        *self.stride = self.range.replace(items.into_iter());
+    }
+
+    /// Removes the items in the group.
+    ///
+    /// ```
+    /// # use editer::groups::edit_groups_by_key;
+    /// #
+    /// let mut items = vec![1, 2, 2, 3, 2];
+    ///
+    /// edit_groups_by_key(&mut items, |item| *item, |group| {
+    ///     if group.len() > 1 {
+    ///         group.remove();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(items, vec![1, 3, 2]);
+    /// ```
+    pub fn remove(self) {
+        *self.stride = 0;
+        self.range.remove();
+    }
+
+    /// Merges the items in the group into the first one, by calling `merge` with the first item
+    /// and each subsequent item in order.
+    ///
+    /// ```
+    /// # use editer::groups::edit_groups_by_key;
+    /// #
+    /// let mut spans = vec![("bold", 3), ("bold", 2), ("plain", 4), ("bold", 1)];
+    ///
+    /// edit_groups_by_key(&mut spans, |span| span.0, |group| {
+    ///     group.merge(|first, span| first.1 += span.1);
+    /// });
+    ///
+    /// assert_eq!(spans, vec![("bold", 5), ("plain", 4), ("bold", 1)]);
+    /// ```
+    pub fn merge(self, merge: impl FnMut(&mut List::Item, List::Item)) {
+        *self.stride = 1;
+        self.range.merge(merge);
+    }
+}
+
+impl<'list, 'stride, List, Key> Deref for GroupSlot<'list, 'stride, List, Key>
+where
+    List: self::List + ?Sized,
+{
+    type Target = RangeSlot<'list, List>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.range
+    }
+}
+
+impl<'list, 'stride, List, Key> DerefMut for GroupSlot<'list, 'stride, List, Key>
+where
+    List: self::List + ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.range
+    }
+}
+
+impl<'list, 'stride, List, Key> Debug for GroupSlot<'list, 'stride, List, Key>
+where
+    List: self::List + ?Sized,
+    List::Item: Debug,
+{
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.range.fmt(formatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edit_groups_by_key;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn visiting_every_group() {
+        let mut items = vec![1, 1, 2, 3, 3, 3, 1];
+        let mut groups = Vec::new();
+
+        edit_groups_by_key(
+            &mut items,
+            |item| *item,
+            |group| groups.push((*group.key(), format!("{:?}", group))),
+        );
+
+        assert_eq!(
+            groups,
+            [
+                (1, String::from("[1, 1]")),
+                (2, String::from("[2]")),
+                (3, String::from("[3, 3, 3]")),
+                (1, String::from("[1]"))
+            ]
+        );
+    }
+
+    #[test]
+    fn grouping_by_a_derived_key() {
+        let mut words = VecDeque::from(["apple", "avocado", "banana", "blueberry", "apricot"]);
+
+        edit_groups_by_key(
+            &mut words,
+            |word| word.chars().next(),
+            |group| {
+                if group.len() > 1 {
+                    group.merge(|_, _| {});
+                }
+            },
+        );
+
+        assert_eq!(words, VecDeque::from(["apple", "banana", "apricot"]));
+    }
+
+    #[test]
+    fn not_regrouping_replacement_items() {
+        let mut items = vec![1, 1, 2, 2];
+        let mut visited = 0;
+
+        edit_groups_by_key(
+            &mut items,
+            |item| *item,
+            |group| {
+                visited += 1;
+
+                if group[0] == 1 {
+                    group.replace([2, 2, 2]);
+                }
+            },
+        );
+
+        assert_eq!(items, vec![2, 2, 2, 2, 2]);
+        assert_eq!(visited, 2);
+    }
+
+    #[test]
+    fn mutating_items_in_a_group() {
+        let mut items = vec![(1, 0), (1, 0), (2, 0)];
+
+        edit_groups_by_key(
+            &mut items,
+            |item| item.0,
+            |mut group| {
+                for index in 0..group.len() {
+                    group[index].1 = index;
+                }
+            },
+        );
+
+        assert_eq!(items, vec![(1, 0), (1, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn editing_an_empty_list() {
+        let mut items: Vec<i32> = Vec::new();
+        let mut visited = 0;
+
+        edit_groups_by_key(&mut items, |item| *item, |_| visited += 1);
+
+        assert_eq!(visited, 0);
+    }
+}
@@ -0,0 +1,340 @@
+//! Mapping indices into a list from before an edit to after it, and vice versa.
+
+use crate::{slot::Slot, walk, Change, List, Stride};
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{convert::Infallible, iter};
+
+/// Like [`edit`](crate::edit), but returns an [`IndexMap`] relating the indices of the items in
+/// `items` before the edit to their indices after it.
+///
+/// The map is built once the edit is done, from a log of the changes made through each [`Slot`],
+/// so it costs little more than the edit itself.
+///
+/// ```
+/// use editer::index_map::{edit_with_index_map, Origin};
+///
+/// let mut items = vec![1, 2, 3, 4, 5];
+///
+/// let map = edit_with_index_map(&mut items, |item| {
+///     if item == 2 {
+///         item.remove();
+///     } else if item == 4 {
+///         item.insert_before([6, 7]);
+///     }
+/// });
+///
+/// assert_eq!(items, vec![1, 3, 6, 7, 4, 5]);
+///
+/// assert_eq!(map.new_index(0), Some(0));
+/// assert_eq!(map.new_index(1), None);
+/// assert_eq!(map.new_index(3), Some(4));
+///
+/// assert_eq!(map.origin(1), Origin::Original(2));
+/// assert_eq!(map.origin(2), Origin::Inserted);
+/// ```
+pub fn edit_with_index_map<List>(items: &mut List, mut edit: impl FnMut(Slot<List>)) -> IndexMap
+where
+    List: self::List + ?Sized,
+{
+    let len = items.len();
+
+    let mut stride = Stride::new();
+    stride.record_changes();
+
+    match walk(items, &mut stride, &mut (), |slot| {
+        edit(slot);
+        Ok::<(), Infallible>(())
+    }) {
+        Ok(()) => IndexMap::replay(len, stride.take_changes()),
+        Err(never) => match never {},
+    }
+}
+
+/// Where an item in an edited list came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Origin {
+    /// The item was in the list before it was edited, at the given index.
+    Original(usize),
+
+    /// The item was inserted while editing the list.
+    Inserted,
+}
+
+/// Relates the indices of the items in a list before it was edited to their indices after.
+///
+/// Returned by [`edit_with_index_map`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexMap {
+    forward: Vec<Option<usize>>,
+    backward: Vec<Origin>,
+}
+
+impl IndexMap {
+    /// Returns the index after the edit of the item at `old_index` before it, or `None` if that
+    /// item was removed or replaced.
+    ///
+    /// Panics if `old_index` is out of bounds for the list before the edit.
+    pub fn new_index(&self, old_index: usize) -> Option<usize> {
+        self.forward[old_index]
+    }
+
+    /// Returns the origin of the item at `new_index` after the edit.
+    ///
+    /// Panics if `new_index` is out of bounds for the list after the edit.
+    pub fn origin(&self, new_index: usize) -> Origin {
+        self.backward[new_index]
+    }
+
+    /// Returns the new index of each item in the list before the edit, by old index.
+    pub fn forward(&self) -> &[Option<usize>] {
+        &self.forward
+    }
+
+    /// Returns the origin of each item in the list after the edit, by new index.
+    pub fn backward(&self) -> &[Origin] {
+        &self.backward
+    }
+
+    /// Works out where the items in a list of length `len` ended up from the `changes` made to
+    /// it in a single pass of an edit, each paired with the index of the slot it was made
+    /// through.
+    ///
+    /// Since the edit visits the list from start to end, the changes can be replayed in one pass
+    /// over the origins of the items, split at the current position.
+    fn replay(len: usize, changes: Vec<(usize, Change)>) -> IndexMap {
+        // The items pushed to the front of the list, in reverse order.
+        let mut front = Vec::new();
+
+        // The items between those and the current position.
+        let mut done = Vec::with_capacity(len);
+
+        // The items from the current position to the end of the list.
+        let mut pending: VecDeque<_> = (0..len).map(Origin::Original).collect();
+
+        // The number of items moved to the end of the list, as in `Stride`.
+        let mut tail = 0;
+
+        let inserted = |count| iter::repeat(Origin::Inserted).take(count);
+
+        for (index, change) in changes {
+            // Catch up with the items passed over without being changed.
+            while front.len() + done.len() < index {
+                done.extend(pending.pop_front());
+            }
+
+            match change {
+                Change::InsertedBefore(count) => done.extend(inserted(count)),
+
+                Change::InsertedAfter(count) => {
+                    done.extend(pending.pop_front());
+                    done.extend(inserted(count));
+                }
+
+                Change::Replaced(count) => {
+                    pending.pop_front();
+                    done.extend(inserted(count));
+                }
+
+                Change::Removed => {
+                    pending.pop_front();
+                }
+
+                Change::PushedFront(count) => front.extend(inserted(count)),
+
+                Change::PushedBack(count) => {
+                    for _ in 0..count {
+                        pending.insert(pending.len() - tail, Origin::Inserted);
+                    }
+                }
+
+                Change::Moved(to) => {
+                    let len = front.len() + done.len() + pending.len();
+                    let origin = pending.pop_front().expect("moved item missing");
+
+                    if to < front.len() {
+                        front.insert(front.len() - to, origin);
+                    } else if to < index {
+                        done.insert(to - front.len(), origin);
+                    } else {
+                        if to + 1 >= len - tail {
+                            tail += 1;
+                        }
+
+                        pending.insert(to - index, origin);
+                    }
+                }
+            }
+        }
+
+        let backward: Vec<_> = front.into_iter().rev().chain(done).chain(pending).collect();
+        let mut forward = alloc::vec![None; len];
+
+        for (new_index, origin) in backward.iter().enumerate() {
+            if let Origin::Original(old_index) = *origin {
+                forward[old_index] = Some(new_index);
+            }
+        }
+
+        IndexMap { forward, backward }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_with_index_map, Origin};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn mapping_an_unchanged_list() {
+        let mut items = vec![1, 2, 3];
+
+        let map = edit_with_index_map(&mut items, |mut item| *item += 1);
+
+        assert_eq!(items, vec![2, 3, 4]);
+        assert_eq!(map.forward(), [Some(0), Some(1), Some(2)]);
+        assert_eq!(
+            map.backward(),
+            [
+                Origin::Original(0),
+                Origin::Original(1),
+                Origin::Original(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn mapping_every_kind_of_change() {
+        let mut items = VecDeque::from([1, 2, 3, 4, 5]);
+
+        let map = edit_with_index_map(&mut items, |item| {
+            if item == 1 {
+                item.insert_after([6]);
+            } else if item == 2 {
+                item.replace([7, 8]);
+            } else if item == 3 {
+                item.remove();
+            } else if item == 5 {
+                item.insert_before([9]);
+            }
+        });
+
+        assert_eq!(items, VecDeque::from([1, 6, 7, 8, 4, 9, 5]));
+        assert_eq!(map.forward(), [Some(0), None, None, Some(4), Some(6)]);
+        assert_eq!(
+            map.backward(),
+            [
+                Origin::Original(0),
+                Origin::Inserted,
+                Origin::Inserted,
+                Origin::Inserted,
+                Origin::Original(3),
+                Origin::Inserted,
+                Origin::Original(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn mapping_an_emptied_list() {
+        let mut items = vec![1, 2, 3];
+
+        let map = edit_with_index_map(&mut items, |item| item.remove());
+
+        assert!(items.is_empty());
+        assert_eq!(map.forward(), [None, None, None]);
+        assert!(map.backward().is_empty());
+    }
+
+    #[test]
+    fn mapping_moved_and_pushed_items() {
+        let mut items = vec![1, 2, 3, 4, 5, 6];
+
+        let map = edit_with_index_map(&mut items, |mut item| {
+            if item == 1 {
+                item.move_by(2);
+            } else if item == 2 {
+                item.push_front([7]);
+                item.push_back([8]);
+            } else if item == 4 {
+                item.move_to_end();
+            } else if item == 5 {
+                item.move_by(-3);
+            }
+        });
+
+        assert_eq!(items, vec![7, 5, 2, 3, 1, 6, 8, 4]);
+        assert_eq!(
+            map.forward(),
+            [Some(4), Some(2), Some(3), Some(7), Some(1), Some(5)]
+        );
+        assert_eq!(
+            map.backward(),
+            [
+                Origin::Inserted,
+                Origin::Original(4),
+                Origin::Original(1),
+                Origin::Original(2),
+                Origin::Original(0),
+                Origin::Original(5),
+                Origin::Inserted,
+                Origin::Original(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn mapping_agrees_with_an_observer() {
+        use crate::{edit_observed, observer::EditObserver, slot::Slot};
+
+        // Tracks origins the slow way, by applying each change to a copy of the list.
+        struct Origins(Vec<Origin>);
+
+        impl EditObserver for Origins {
+            fn on_insert(&mut self, index: usize, count: usize) {
+                let inserted = std::iter::repeat(Origin::Inserted).take(count);
+                self.0.splice(index..index, inserted);
+            }
+
+            fn on_remove(&mut self, index: usize) {
+                self.0.remove(index);
+            }
+
+            fn on_replace(&mut self, index: usize, count: usize) {
+                let inserted = std::iter::repeat(Origin::Inserted).take(count);
+                self.0.splice(index..index + 1, inserted);
+            }
+
+            fn on_move(&mut self, from: usize, to: usize) {
+                let origin = self.0.remove(from);
+                self.0.insert(to, origin);
+            }
+        }
+
+        fn edit(mut item: Slot<Vec<u32>, impl EditObserver>) {
+            match *item % 9 {
+                0 => item.remove(),
+                1 => item.insert_before([100]),
+                2 => item.insert_after([100, 100]),
+                3 => item.replace([100, 100]),
+                4 if *item < 40 => item.move_by(2),
+                5 => item.move_to_end(),
+                6 => item.move_to_front(),
+                7 if *item < 50 => item.push_back([*item + 50]),
+                8 => item.push_front([100]),
+                _ => *item += 1,
+            }
+        }
+
+        for step in [7, 11, 13, 17] {
+            let mut items: Vec<u32> = (0..50).map(|index| index * step % 50).collect();
+            let mut expected = items.clone();
+            let mut origins = Origins((0..items.len()).map(Origin::Original).collect());
+
+            let map = edit_with_index_map(&mut items, edit);
+            edit_observed(&mut expected, &mut origins, edit);
+
+            assert_eq!(items, expected);
+            assert_eq!(map.backward(), origins.0);
+        }
+    }
+}
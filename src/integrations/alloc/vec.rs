@@ -1,7 +1,8 @@
 extern crate alloc;
 
-use crate::List;
+use crate::{integrations::move_within, List};
 use alloc::vec::Vec;
+use core::ops::Range;
 
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 impl<Item> List for Vec<Item> {
@@ -23,13 +24,21 @@ impl<Item> List for Vec<Item> {
         Vec::splice(self, index..index, items);
     }
 
-    fn remove(&mut self, index: usize) {
-        Vec::remove(self, index);
+    fn remove(&mut self, index: usize) -> Item {
+        Vec::remove(self, index)
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        move_within(self, from, to);
     }
 
     fn replace(&mut self, index: usize, items: impl Iterator<Item = Item>) {
         Vec::splice(self, index..index + 1, items);
     }
+
+    fn replace_range(&mut self, range: Range<usize>, items: impl Iterator<Item = Self::Item>) {
+        Vec::splice(self, range, items);
+    }
 }
 
 #[cfg(test)]
@@ -269,4 +278,9 @@ mod tests {
 
         assert_eq!(items, vec![1, 2, 3, 4, 5, 6, 7, 8]);
     }
+
+    #[test]
+    fn moving_and_pushing_items() {
+        crate::integrations::tests::moving_and_pushing_items(|items| items.to_vec());
+    }
 }
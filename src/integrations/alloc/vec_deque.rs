@@ -27,8 +27,22 @@ impl<Item> List for VecDeque<Item> {
         }
     }
 
-    fn remove(&mut self, index: usize) {
-        VecDeque::remove(self, index);
+    fn remove(&mut self, index: usize) -> Item {
+        VecDeque::remove(self, index).expect("removal index out of bounds")
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        // Swapping neighbours works across the two halves of the ring buffer, unlike rotating a
+        // slice, which would mean making the whole deque contiguous first.
+        if from < to {
+            for index in from..to {
+                VecDeque::swap(self, index, index + 1);
+            }
+        } else {
+            for index in (to..from).rev() {
+                VecDeque::swap(self, index, index + 1);
+            }
+        }
     }
 }
 
@@ -270,4 +284,29 @@ mod tests {
 
         assert_eq!(items, VecDeque::from([1, 2, 3, 4, 5, 6, 7, 8]));
     }
+
+    #[test]
+    fn moving_items_in_a_wrapped_deque() {
+        let mut items = VecDeque::with_capacity(8);
+        items.extend([3, 4, 5]);
+        items.push_front(2);
+        items.push_front(1);
+
+        edit(&mut items, |item| {
+            if item == 1 {
+                item.move_by(3);
+            } else if item == 5 {
+                item.move_to_front();
+            }
+        });
+
+        assert_eq!(items, VecDeque::from([5, 2, 3, 4, 1]));
+    }
+
+    #[test]
+    fn moving_and_pushing_items() {
+        crate::integrations::tests::moving_and_pushing_items(|items| {
+            VecDeque::from(items.to_vec())
+        });
+    }
 }
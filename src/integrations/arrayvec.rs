@@ -1,4 +1,4 @@
-use crate::List;
+use crate::{integrations::move_within, List};
 use arrayvec::ArrayVec;
 
 #[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
@@ -23,8 +23,12 @@ impl<Item, const CAP: usize> List for ArrayVec<Item, CAP> {
         }
     }
 
-    fn remove(&mut self, index: usize) {
-        ArrayVec::remove(self, index);
+    fn remove(&mut self, index: usize) -> Item {
+        ArrayVec::remove(self, index)
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        move_within(self, from, to);
     }
 }
 
@@ -266,4 +270,11 @@ mod tests {
 
         assert_eq!(items, ArrayVec::from_iter([1, 2, 3, 4, 5, 6, 7, 8]));
     }
+
+    #[test]
+    fn moving_and_pushing_items() {
+        crate::integrations::tests::moving_and_pushing_items(|items| {
+            ArrayVec::<_, 10>::from_iter(items.iter().copied())
+        });
+    }
 }
@@ -9,3 +9,123 @@ mod smallvec;
 
 #[cfg(feature = "tinyvec")]
 mod tinyvec;
+
+/// Moves the item at `from` to `to` by rotating the items in between.
+#[cfg(any(
+    feature = "alloc",
+    feature = "arrayvec",
+    feature = "smallvec",
+    feature = "tinyvec"
+))]
+fn move_within<Item>(items: &mut [Item], from: usize, to: usize) {
+    if from < to {
+        items[from..=to].rotate_left(1);
+    } else {
+        items[to..=from].rotate_right(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{edit, slot::Slot, List};
+
+    /// Moves and pushes items around lists made by `new`, which every integration should handle
+    /// the same way.
+    pub(super) fn moving_and_pushing_items<Items>(new: impl Fn(&[i32]) -> Items)
+    where
+        Items: List<Item = i32>,
+    {
+        let check = |items: &[i32], edit_item: fn(Slot<Items>), expected: &[i32]| {
+            let mut items = new(items);
+            edit(&mut items, edit_item);
+
+            let items: Vec<_> = (0..items.len()).map(|index| *items.index(index)).collect();
+            assert_eq!(items, expected);
+        };
+
+        // To the front.
+        check(
+            &[1, 2, 3, 4, 5],
+            |item| {
+                if *item % 2 == 0 {
+                    item.move_to_front();
+                }
+            },
+            &[4, 2, 1, 3, 5],
+        );
+
+        // To the end.
+        check(
+            &[1, 2, 3, 4, 5],
+            |item| {
+                if *item % 2 == 0 {
+                    item.move_to_end();
+                }
+            },
+            &[1, 3, 5, 2, 4],
+        );
+
+        // Forward.
+        check(
+            &[1, 2, 3, 4, 5],
+            |item| {
+                if item == 1 || item == 2 {
+                    item.move_by(2);
+                }
+            },
+            &[3, 1, 2, 4, 5],
+        );
+
+        // Backward.
+        check(
+            &[1, 2, 3, 4, 5],
+            |item| {
+                if item == 4 {
+                    item.move_by(-2);
+                } else if item == 5 {
+                    item.move_by(-4);
+                }
+            },
+            &[5, 1, 4, 2, 3],
+        );
+
+        // Pushing to the back.
+        check(
+            &[1, 3],
+            |mut item| {
+                if item < 4 {
+                    item.push_back([*item * 2]);
+                }
+            },
+            &[1, 3, 2, 6, 4],
+        );
+
+        // Pushing to the back, before items moved to the end.
+        check(
+            &[1, 2, 3],
+            |mut item| {
+                if item == 1 {
+                    item.move_to_end();
+                } else if item == 2 {
+                    item.push_back([4]);
+                    item.move_by(1);
+                }
+            },
+            &[3, 2, 4, 1],
+        );
+
+        // Pushing to the front.
+        check(
+            &[1, 2, 3],
+            |mut item| {
+                if item == 2 {
+                    item.push_front([4, 5]);
+                    item.remove();
+                } else {
+                    *item *= 10;
+                }
+            },
+            &[4, 5, 10, 30],
+        );
+    }
+}
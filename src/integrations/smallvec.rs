@@ -1,4 +1,4 @@
-use crate::List;
+use crate::{integrations::move_within, List};
 use smallvec::SmallVec;
 
 #[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
@@ -28,8 +28,12 @@ where
         }
     }
 
-    fn remove(&mut self, index: usize) {
-        SmallVec::remove(self, index);
+    fn remove(&mut self, index: usize) -> Self::Item {
+        SmallVec::remove(self, index)
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        move_within(self, from, to);
     }
 }
 
@@ -271,4 +275,11 @@ mod tests {
 
         assert_eq!(items, SmallVec::from([1, 2, 3, 4, 5, 6, 7, 8]));
     }
+
+    #[test]
+    fn moving_and_pushing_items() {
+        crate::integrations::tests::moving_and_pushing_items(|items| {
+            SmallVec::<[_; 5]>::from_slice(items)
+        });
+    }
 }
@@ -1,4 +1,5 @@
-use crate::List;
+use crate::{integrations::move_within, List};
+use core::ops::Range;
 use tinyvec::TinyVec;
 
 #[cfg_attr(docsrs, doc(cfg(feature = "tinyvec")))]
@@ -28,13 +29,21 @@ where
         TinyVec::splice(self, index..index, items);
     }
 
-    fn remove(&mut self, index: usize) {
-        TinyVec::remove(self, index);
+    fn remove(&mut self, index: usize) -> Self::Item {
+        TinyVec::remove(self, index)
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        move_within(self, from, to);
     }
 
     fn replace(&mut self, index: usize, items: impl Iterator<Item = Self::Item>) {
         TinyVec::splice(self, index..index + 1, items);
     }
+
+    fn replace_range(&mut self, range: Range<usize>, items: impl Iterator<Item = Self::Item>) {
+        TinyVec::splice(self, range, items);
+    }
 }
 
 #[cfg(test)]
@@ -275,4 +284,11 @@ mod tests {
 
         assert_eq!(items, TinyVec::from_iter([1, 2, 3, 4, 5, 6, 7, 8]));
     }
+
+    #[test]
+    fn moving_and_pushing_items() {
+        crate::integrations::tests::moving_and_pushing_items(|items| {
+            TinyVec::<[_; 10]>::from_iter(items.iter().copied())
+        });
+    }
 }
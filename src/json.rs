@@ -0,0 +1,832 @@
+//! Editing [`serde_json::Value`] documents in place.
+//!
+//! JSON arrays are `Vec<Value>`s, so they're [`List`](crate::List)s already, and can be edited
+//! with [`edit`](crate::edit) directly. [`edit_json`] walks a whole document instead, visiting
+//! the elements of every array and the entries of every object.
+//!
+//! [`serde_json::Value`]: https://docs.rs/serde_json/latest/serde_json/enum.Value.html
+
+use crate::{slot::Slot, Stride};
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Debug, Display},
+    mem,
+    ops::{Deref, DerefMut},
+};
+use serde_json::{
+    map::{self, Entry},
+    Map, Value,
+};
+
+/// Walks the arrays and objects in `value` depth-first, calling `edit` with a [`JsonSlot`] for
+/// each array element and object entry. The `JsonSlot` allows accessing the current value and/or
+/// updating the array or object containing it.
+///
+/// Each value is visited before the values nested in it. The values nested in a value that's
+/// replaced or removed aren't visited, and neither are values inserted or used as replacements.
+///
+/// Each object is rebuilt in order as its entries are visited, so with serde_json's
+/// `preserve_order` feature, entries inserted through an [`EntrySlot`] end up where the entry was
+/// rather than at the end of the object. If `edit` panics, the entries yet to be visited are put
+/// back.
+///
+/// ```
+/// use editer::json::{edit_json, JsonSlot};
+/// use serde_json::json;
+///
+/// let mut document = json!({
+///     "name": "editer",
+///     "internal": true,
+///     "tags": ["rust", "lists"],
+///     "authors": [{ "name": "a", "internal": true }]
+/// });
+///
+/// edit_json(&mut document, |slot| match slot {
+///     JsonSlot::Entry(entry) if entry.key() == "internal" => entry.remove(),
+///     JsonSlot::Element(element) if element == "lists" => {
+///         element.replace(["iteration".into(), "mutation".into()])
+///     }
+///     _ => {}
+/// });
+///
+/// assert_eq!(
+///     document,
+///     json!({
+///         "name": "editer",
+///         "tags": ["rust", "iteration", "mutation"],
+///         "authors": [{ "name": "a" }]
+///     })
+/// );
+/// ```
+pub fn edit_json(value: &mut Value, mut edit: impl FnMut(JsonSlot)) {
+    let mut path = Vec::new();
+
+    // Keeping the arrays and objects being walked on an explicit stack rather than recursing means
+    // deeply nested documents can't overflow the call stack.
+    let mut stack = Stack(Vec::new());
+    stack.0.extend(Frame::new(value));
+
+    while let Some(frame) = stack.0.last_mut() {
+        let (segment, value) = match frame {
+            Frame::Array {
+                elements,
+                stride,
+                index,
+            } => {
+                // SAFETY: See `Stack`.
+                let elements = unsafe { &mut **elements };
+
+                if *index >= elements.len() - stride.tail() {
+                    stack.pop(&mut path);
+                    continue;
+                }
+
+                if stride.skip(elements.len() - *index) {
+                    *index += 1;
+                    continue;
+                }
+
+                path.push(PathSegment::Index(*index));
+                let mut descend = Some(*index);
+
+                edit(JsonSlot::Element(ElementSlot {
+                    slot: Slot::new(&mut *elements, *index, stride, &mut ()),
+                    index: *index,
+                    path: &path,
+                    descend: &mut descend,
+                }));
+
+                path.pop();
+                *index += stride.advance();
+
+                match descend {
+                    Some(index) => (PathSegment::Index(index), &mut elements[index]),
+                    None => continue,
+                }
+            }
+
+            Frame::Object {
+                entries,
+                pending,
+                current,
+                removed,
+            } => {
+                // SAFETY: See `Stack`.
+                let entries = unsafe { &mut **entries };
+
+                let (key, value) = match pending.next() {
+                    Some(entry) => entry,
+                    None => {
+                        stack.pop(&mut path);
+                        continue;
+                    }
+                };
+
+                // An entry inserted or renamed earlier has overwritten this one.
+                if entries.contains_key(&key) {
+                    continue;
+                }
+
+                path.push(PathSegment::Key(key.clone()));
+                let (key, value) = current.insert((key, value));
+                *removed = false;
+
+                edit(JsonSlot::Entry(EntrySlot {
+                    entries,
+                    key,
+                    value,
+                    path: &path,
+                    removed,
+                }));
+
+                let segment = path.pop();
+
+                let (key, value) = match current.take() {
+                    Some(entry) if !*removed => entry,
+                    _ => continue,
+                };
+
+                // Reuse the key in the path, unless the entry was renamed.
+                let segment = match segment {
+                    Some(PathSegment::Key(original)) if original == key => {
+                        PathSegment::Key(original)
+                    }
+                    _ => PathSegment::Key(key.clone()),
+                };
+
+                let value = match entries.entry(key) {
+                    Entry::Vacant(entry) => entry.insert(value),
+                    Entry::Occupied(mut entry) => {
+                        entry.insert(value);
+                        entry.into_mut()
+                    }
+                };
+
+                (segment, value)
+            }
+        };
+
+        if let Some(frame) = Frame::new(value) {
+            path.push(segment);
+            stack.0.push(frame);
+        }
+    }
+}
+
+/// The arrays and objects being walked by [`edit_json`], from the outermost to the innermost.
+///
+/// Each frame points to either the document passed to `edit_json` or a value in the array or
+/// object of the frame below. Only the top frame is accessed, so the frames below it, and the
+/// values they point to, are left alone until it's popped.
+struct Stack(Vec<Frame>);
+
+impl Stack {
+    /// Pops the top frame, and the segment of `path` leading to it, if any.
+    fn pop(&mut self, path: &mut Vec<PathSegment>) {
+        self.0.pop();
+
+        if !self.0.is_empty() {
+            path.pop();
+        }
+    }
+}
+
+impl Drop for Stack {
+    fn drop(&mut self) {
+        // Pop the frames from the innermost out, so that each one is dropped while the value it
+        // points to is still in place.
+        while self.0.pop().is_some() {}
+    }
+}
+
+/// An array or object being walked by [`edit_json`].
+enum Frame {
+    Array {
+        elements: *mut Vec<Value>,
+        stride: Stride,
+
+        /// The position of the next element to visit.
+        index: usize,
+    },
+
+    /// An object, which is emptied and then rebuilt as its entries are visited.
+    Object {
+        entries: *mut Map<String, Value>,
+
+        /// The entries yet to be visited.
+        pending: map::IntoIter,
+
+        /// The entry being visited, while `edit` is called with it.
+        current: Option<(String, Value)>,
+
+        /// Whether the entry being visited has been removed or replaced.
+        removed: bool,
+    },
+}
+
+impl Frame {
+    fn new(value: &mut Value) -> Option<Frame> {
+        match value {
+            Value::Array(elements) => Some(Frame::Array {
+                elements,
+                stride: Stride::new(),
+                index: 0,
+            }),
+            Value::Object(entries) => Some(Frame::Object {
+                pending: mem::take(entries).into_iter(),
+                entries,
+                current: None,
+                removed: false,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Drop for Frame {
+    fn drop(&mut self) {
+        // Put back the entries of an object that weren't visited because `edit` panicked.
+        if let Frame::Object {
+            entries,
+            pending,
+            current,
+            removed,
+        } = self
+        {
+            // SAFETY: See `Stack`.
+            let entries = unsafe { &mut **entries };
+            let current = current.take().filter(|_| !*removed);
+
+            for (key, value) in current.into_iter().chain(pending) {
+                entries.entry(key).or_insert(value);
+            }
+        }
+    }
+}
+
+/// A step along the path from the root of a JSON document to a value nested in it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// The index of an element in an array.
+    Index(usize),
+
+    /// The key of an entry in an object.
+    Key(String),
+}
+
+impl Display for PathSegment {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathSegment::Index(index) => write!(formatter, "[{}]", index),
+            PathSegment::Key(key) => write!(formatter, ".{}", key),
+        }
+    }
+}
+
+/// The kind of value containing the current value in an [`edit_json`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParentKind {
+    /// The current value is an element of an array.
+    Array,
+
+    /// The current value is the value of an entry in an object.
+    Object,
+}
+
+/// Represents the current value in an [`edit_json`]: either an element of an array or an entry
+/// in an object.
+pub enum JsonSlot<'a> {
+    /// An element of an array.
+    Element(ElementSlot<'a>),
+
+    /// An entry in an object.
+    Entry(EntrySlot<'a>),
+}
+
+impl<'a> JsonSlot<'a> {
+    /// Returns the path from the root of the document to the current value.
+    ///
+    /// Array indices in the path are the current positions of the elements, which may differ
+    /// from their original positions if the arrays have been edited.
+    ///
+    /// ```
+    /// use editer::json::{edit_json, PathSegment};
+    /// use serde_json::json;
+    ///
+    /// let mut document = json!({ "a": [{ "b": 1 }] });
+    /// let mut paths = Vec::new();
+    ///
+    /// edit_json(&mut document, |slot| {
+    ///     paths.push(slot.path().iter().map(PathSegment::to_string).collect::<String>())
+    /// });
+    ///
+    /// assert_eq!(paths, [".a", ".a[0]", ".a[0].b"]);
+    /// ```
+    pub fn path(&self) -> &[PathSegment] {
+        match self {
+            JsonSlot::Element(element) => element.path,
+            JsonSlot::Entry(entry) => entry.path,
+        }
+    }
+
+    /// Returns the kind of value containing the current value.
+    pub fn parent_kind(&self) -> ParentKind {
+        match self {
+            JsonSlot::Element(_) => ParentKind::Array,
+            JsonSlot::Entry(_) => ParentKind::Object,
+        }
+    }
+
+    /// Returns a shared reference to the current value.
+    pub fn get(&self) -> &Value {
+        match self {
+            JsonSlot::Element(element) => element.get(),
+            JsonSlot::Entry(entry) => entry.get(),
+        }
+    }
+
+    /// Returns a mutable reference to the current value.
+    pub fn get_mut(&mut self) -> &mut Value {
+        match self {
+            JsonSlot::Element(element) => element.get_mut(),
+            JsonSlot::Entry(entry) => entry.get_mut(),
+        }
+    }
+
+    /// Removes the current array element or object entry.
+    ///
+    /// ```
+    /// use editer::json::edit_json;
+    /// use serde_json::json;
+    ///
+    /// let mut document = json!({ "a": null, "b": [1, null, 2] });
+    ///
+    /// edit_json(&mut document, |slot| {
+    ///     if slot.is_null() {
+    ///         slot.remove();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(document, json!({ "b": [1, 2] }));
+    /// ```
+    pub fn remove(self) {
+        match self {
+            JsonSlot::Element(element) => element.remove(),
+            JsonSlot::Entry(entry) => entry.remove(),
+        }
+    }
+}
+
+impl<'a> Deref for JsonSlot<'a> {
+    type Target = Value;
+
+    fn deref(&self) -> &Value {
+        self.get()
+    }
+}
+
+impl<'a> DerefMut for JsonSlot<'a> {
+    fn deref_mut(&mut self) -> &mut Value {
+        self.get_mut()
+    }
+}
+
+impl<'a, Other> PartialEq<Other> for JsonSlot<'a>
+where
+    Value: PartialEq<Other>,
+{
+    fn eq(&self, other: &Other) -> bool {
+        *self.get() == *other
+    }
+}
+
+impl<'a> Debug for JsonSlot<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonSlot::Element(element) => element.fmt(formatter),
+            JsonSlot::Entry(entry) => entry.fmt(formatter),
+        }
+    }
+}
+
+/// Represents the current element of an array in an [`edit_json`].
+///
+/// It works like a [`Slot`] for the array.
+pub struct ElementSlot<'a> {
+    slot: Slot<'a, 'a, Vec<Value>>,
+    index: usize,
+    path: &'a [PathSegment],
+    descend: &'a mut Option<usize>,
+}
+
+impl<'a> ElementSlot<'a> {
+    /// Returns the current index of the element in its array.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns a shared reference to the element.
+    pub fn get(&self) -> &Value {
+        self.slot.get()
+    }
+
+    /// Returns a mutable reference to the element.
+    pub fn get_mut(&mut self) -> &mut Value {
+        self.slot.get_mut()
+    }
+
+    /// Inserts zero or more `values` before the element.
+    pub fn insert_before<Values>(self, values: Values)
+    where
+        Values: IntoIterator<Item = Value>,
+        Values::IntoIter: ExactSizeIterator,
+    {
+        let values = values.into_iter();
+
+        if let Some(index) = self.descend {
+            *index += values.len();
+        }
+
+        self.slot.insert_before(values);
+    }
+
+    /// Inserts zero or more `values` after the element.
+    pub fn insert_after<Values>(self, values: Values)
+    where
+        Values: IntoIterator<Item = Value>,
+        Values::IntoIter: ExactSizeIterator,
+    {
+        self.slot.insert_after(values);
+    }
+
+    /// Replaces the element with zero or more `values`.
+    pub fn replace<Values>(self, values: Values)
+    where
+        Values: IntoIterator<Item = Value>,
+        Values::IntoIter: ExactSizeIterator,
+    {
+        *self.descend = None;
+        self.slot.replace(values);
+    }
+
+    /// Removes the element.
+    pub fn remove(self) {
+        *self.descend = None;
+        self.slot.remove();
+    }
+}
+
+impl<'a> Deref for ElementSlot<'a> {
+    type Target = Value;
+
+    fn deref(&self) -> &Value {
+        self.get()
+    }
+}
+
+impl<'a> DerefMut for ElementSlot<'a> {
+    fn deref_mut(&mut self) -> &mut Value {
+        self.get_mut()
+    }
+}
+
+impl<'a, Other> PartialEq<Other> for ElementSlot<'a>
+where
+    Value: PartialEq<Other>,
+{
+    fn eq(&self, other: &Other) -> bool {
+        *self.get() == *other
+    }
+}
+
+impl<'a> Debug for ElementSlot<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("ElementSlot")
+            .field("index", &self.index())
+            .field("value", self.get())
+            .finish()
+    }
+}
+
+/// Represents the current entry in an object in an [`edit_json`].
+///
+/// Entries inserted into the object aren't visited, and nor are the entries yet to be visited that
+/// they overwrite.
+pub struct EntrySlot<'a> {
+    entries: &'a mut Map<String, Value>,
+    key: &'a mut String,
+    value: &'a mut Value,
+    path: &'a [PathSegment],
+    removed: &'a mut bool,
+}
+
+impl<'a> EntrySlot<'a> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &str {
+        self.key
+    }
+
+    /// Returns a shared reference to the value of the entry.
+    pub fn get(&self) -> &Value {
+        self.value
+    }
+
+    /// Returns a mutable reference to the value of the entry.
+    pub fn get_mut(&mut self) -> &mut Value {
+        self.value
+    }
+
+    /// Inserts zero or more `entries` into the object before the entry, overwriting any other
+    /// entries with the same keys. An entry with the same key as this one is overwritten by it.
+    pub fn insert(self, entries: impl IntoIterator<Item = (String, Value)>) {
+        self.entries.extend(entries);
+    }
+
+    /// Replaces the entry with zero or more `entries`, overwriting any other entries with the
+    /// same keys.
+    ///
+    /// ```
+    /// use editer::json::{edit_json, JsonSlot};
+    /// use serde_json::json;
+    ///
+    /// let mut document = json!({ "size": { "width": 1, "height": 2 } });
+    ///
+    /// edit_json(&mut document, |slot| {
+    ///     if let JsonSlot::Entry(entry) = slot {
+    ///         if entry.key() == "size" {
+    ///             let size = entry.get().as_object().unwrap().clone();
+    ///             entry.replace(size);
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(document, json!({ "width": 1, "height": 2 }));
+    /// ```
+    pub fn replace(self, entries: impl IntoIterator<Item = (String, Value)>) {
+        *self.removed = true;
+        self.entries.extend(entries);
+    }
+
+    /// Moves the value of the entry to a new entry with the key `key`, overwriting any other
+    /// entry with that key. The value is walked under its new key, and an entry with that key
+    /// that's yet to be visited is overwritten without being visited.
+    ///
+    /// ```
+    /// use editer::json::{edit_json, JsonSlot};
+    /// use serde_json::json;
+    ///
+    /// let mut document = json!({ "user": { "userName": "a" } });
+    ///
+    /// edit_json(&mut document, |slot| {
+    ///     if let JsonSlot::Entry(entry) = slot {
+    ///         if entry.key() == "userName" {
+    ///             entry.rename("user_name");
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(document, json!({ "user": { "user_name": "a" } }));
+    /// ```
+    pub fn rename(self, key: impl Into<String>) {
+        *self.key = key.into();
+    }
+
+    /// Removes the entry.
+    pub fn remove(self) {
+        *self.removed = true;
+    }
+}
+
+impl<'a> Deref for EntrySlot<'a> {
+    type Target = Value;
+
+    fn deref(&self) -> &Value {
+        self.get()
+    }
+}
+
+impl<'a> DerefMut for EntrySlot<'a> {
+    fn deref_mut(&mut self) -> &mut Value {
+        self.get_mut()
+    }
+}
+
+impl<'a, Other> PartialEq<Other> for EntrySlot<'a>
+where
+    Value: PartialEq<Other>,
+{
+    fn eq(&self, other: &Other) -> bool {
+        *self.get() == *other
+    }
+}
+
+impl<'a> Debug for EntrySlot<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("EntrySlot")
+            .field("key", self.key)
+            .field("value", self.get())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_json, JsonSlot, ParentKind, PathSegment};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn visiting_every_nested_value() {
+        let mut document = json!({ "a": [1, [2]], "b": { "c": 3 } });
+        let mut visited = Vec::new();
+
+        edit_json(&mut document, |slot| {
+            visited.push((slot.path().to_vec(), slot.parent_kind()))
+        });
+
+        assert_eq!(
+            visited,
+            [
+                (vec![PathSegment::Key("a".into())], ParentKind::Object),
+                (
+                    vec![PathSegment::Key("a".into()), PathSegment::Index(0)],
+                    ParentKind::Array
+                ),
+                (
+                    vec![PathSegment::Key("a".into()), PathSegment::Index(1)],
+                    ParentKind::Array
+                ),
+                (
+                    vec![
+                        PathSegment::Key("a".into()),
+                        PathSegment::Index(1),
+                        PathSegment::Index(0)
+                    ],
+                    ParentKind::Array
+                ),
+                (vec![PathSegment::Key("b".into())], ParentKind::Object),
+                (
+                    vec![PathSegment::Key("b".into()), PathSegment::Key("c".into())],
+                    ParentKind::Object
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn not_visiting_a_scalar_document() {
+        let mut document = json!(1);
+        let mut visited = 0;
+
+        edit_json(&mut document, |_| visited += 1);
+
+        assert_eq!(visited, 0);
+    }
+
+    #[test]
+    fn descending_into_elements_after_inserting_siblings() {
+        let mut document = json!([[1], [2]]);
+        let mut visited = Vec::new();
+
+        edit_json(&mut document, |slot| {
+            let path = slot.path().to_vec();
+            visited.push(path);
+
+            if let JsonSlot::Element(element) = slot {
+                if element == json!([1]) {
+                    element.insert_before([json!(0)]);
+                } else if element == json!([2]) {
+                    element.insert_after([json!([3])]);
+                }
+            }
+        });
+
+        assert_eq!(document, json!([0, [1], [2], [3]]));
+        assert_eq!(
+            visited,
+            [
+                vec![PathSegment::Index(0)],
+                vec![PathSegment::Index(1), PathSegment::Index(0)],
+                vec![PathSegment::Index(2)],
+                vec![PathSegment::Index(2), PathSegment::Index(0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn not_visiting_inserted_entries() {
+        let mut document = json!({ "a": 1, "b": 2 });
+        let mut visited = Vec::new();
+
+        edit_json(&mut document, |slot| {
+            if let JsonSlot::Entry(entry) = slot {
+                visited.push(String::from(entry.key()));
+
+                if entry.key() == "a" {
+                    entry.insert([(String::from("aa"), json!(3))]);
+                }
+            }
+        });
+
+        assert_eq!(visited, ["a", "b"]);
+        assert_eq!(document, json!({ "a": 1, "aa": 3, "b": 2 }));
+    }
+
+    #[test]
+    fn descending_into_renamed_entries() {
+        let mut document = json!({ "a": { "b": { "c": null } } });
+
+        edit_json(&mut document, |slot| {
+            if let JsonSlot::Entry(entry) = slot {
+                let key = entry.key().to_uppercase();
+                entry.rename(key);
+            }
+        });
+
+        assert_eq!(document, json!({ "A": { "B": { "C": null } } }));
+    }
+
+    #[test]
+    fn mutating_values_in_place() {
+        let mut document = json!({ "counts": [1, 2], "total": 0 });
+
+        edit_json(&mut document, |mut slot| {
+            if let Value::Number(number) = slot.get() {
+                let doubled = number.as_i64().unwrap() * 2;
+                *slot = json!(doubled);
+            }
+        });
+
+        assert_eq!(document, json!({ "counts": [2, 4], "total": 0 }));
+    }
+
+    #[test]
+    fn renaming_onto_an_entry_yet_to_be_visited() {
+        let mut document = json!({ "a": [1], "b": 2 });
+        let mut visited = Vec::new();
+
+        edit_json(&mut document, |slot| {
+            visited.push(
+                slot.path()
+                    .iter()
+                    .map(PathSegment::to_string)
+                    .collect::<String>(),
+            );
+
+            if let JsonSlot::Entry(entry) = slot {
+                if entry.key() == "a" {
+                    entry.rename("b");
+                }
+            }
+        });
+
+        assert_eq!(visited, [".a", ".b[0]"]);
+        assert_eq!(document, json!({ "b": [1] }));
+    }
+
+    #[test]
+    fn walking_a_deeply_nested_document() {
+        const DEPTH: usize = 100_000;
+
+        let mut document = json!(null);
+
+        for _ in 0..DEPTH {
+            document = Value::Array(vec![document]);
+        }
+
+        let (mut visited, mut deepest) = (0, 0);
+
+        edit_json(&mut document, |slot| {
+            visited += 1;
+            deepest = deepest.max(slot.path().len());
+        });
+
+        assert_eq!((visited, deepest), (DEPTH, DEPTH));
+
+        // Take the document apart one level at a time, since dropping it whole would recurse too.
+        while let Value::Array(mut elements) = document {
+            document = elements.pop().unwrap_or(Value::Null);
+        }
+    }
+
+    #[test]
+    fn putting_back_unvisited_entries_after_a_panic() {
+        let mut document = json!({ "a": 1, "b": { "c": 2, "d": 3 }, "e": 4 });
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            edit_json(&mut document, |slot| {
+                if let JsonSlot::Entry(entry) = slot {
+                    if entry.key() == "a" {
+                        entry.remove();
+                    } else if entry.key() == "c" {
+                        panic!("whoops");
+                    }
+                }
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(document, json!({ "b": { "c": 2, "d": 3 }, "e": 4 }));
+    }
+}
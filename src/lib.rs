@@ -60,6 +60,25 @@
 //!   assert_eq!(items, vec![1, 2, 4, 5]);
 //!   ```
 //!
+//! * Move the current item elsewhere in the list using [`Slot::move_to_front`],
+//!   [`Slot::move_to_end`] or [`Slot::move_by`]. Moved items aren't visited again.
+//!
+//!   ```
+//!   # use editer::edit;
+//!   #
+//!   let mut items = vec![1, 2, 3, 4, 5];
+//!
+//!   edit(&mut items, |item| {
+//!       if item == 2 {
+//!           item.move_to_end();
+//!       } else if item == 4 {
+//!           item.move_to_front();
+//!       }
+//!   });
+//!
+//!   assert_eq!(items, vec![4, 1, 3, 5, 2]);
+//!   ```
+//!
 //! [`try_edit`] is the fallible version of `edit`. It applies the given editor function to each
 //! item in the given list, like `edit`. It stops at the first error and returns it.
 //!
@@ -88,15 +107,24 @@
 //! * The **`alloc`** feature, which is enabled by default, implements `List` for [`Vec`] and
 //!   [`VecDeque`].
 //! * The **`arrayvec`** feature implements `List` for [`arrayvec::ArrayVec`].
+//! * The **`memchr`** feature speeds up [`matching::edit_where_byte`] using [`memchr`].
+//! * The **`serde`** feature implements `Serialize` and `Deserialize` for [`patch::Patch`] and
+//!   related types.
+//! * The **`serde_json`** feature, together with `alloc`, provides [`json::edit_json`] for editing
+//!   JSON documents in place.
 //! * The **`smallvec`** feature implements `List` for [`smallvec::SmallVec`].
 //! * The **`tinyvec`** feature implements `List` for [`tinyvec::ArrayVec`] and
 //!   [`tinyvec::TinyVec`].
 //!
+//! Recent releases of serde and serde_json need a newer Rust than editer's minimum of 1.57. To use
+//! the `serde` or `serde_json` feature with Rust 1.57, pin them to older releases in your lockfile.
+//!
 //! Editer can be used without the standard library by disabling default features.
 //!
 //! [`DerefMut::deref_mut`]: core::ops::DerefMut::deref_mut
 //! [`VecDeque`]: https://doc.rust-lang.org/alloc/collections/vec_deque/struct.VecDeque.html
 //! [`arrayvec::ArrayVec`]: https://docs.rs/arrayvec/latest/arrayvec/struct.ArrayVec.html
+//! [`memchr`]: https://docs.rs/memchr/latest/memchr/
 //! [`smallvec::SmallVec`]: https://docs.rs/smallvec/latest/smallvec/struct.SmallVec.html
 //! [`tinyvec::ArrayVec`]: https://docs.rs/tinyvec/latest/tinyvec/struct.ArrayVec.html
 //! [`tinyvec::TinyVec`]: https://docs.rs/tinyvec/latest/tinyvec/enum.TinyVec.html
@@ -104,11 +132,80 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod slot;
 use self::slot::Slot;
 
+pub mod chain;
+
+pub mod chunks;
+
+pub mod editor;
+
+pub mod flat;
+
+pub mod groups;
+
+pub mod matching;
+
+pub mod observer;
+use self::observer::EditObserver;
+
+pub mod range;
+
+pub mod sparse;
+
+pub mod summary;
+use self::summary::EditSummary;
+
+pub mod windows;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod anchor;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod index_map;
+
+#[cfg(all(feature = "alloc", feature = "serde_json"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "serde_json"))))]
+pub mod json;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod map;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod patch;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod reconcile;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod rewriter;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod subsequence;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod tree;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod zip;
+
 mod integrations;
 
+use core::{convert::Infallible, iter, ops::Range};
+
 /// Iterates over `items`, calling `edit` with a [`Slot`] for each item. The `Slot` allows
 /// accessing the current item and/or updating the list at the current position.
 ///
@@ -125,16 +222,172 @@ mod integrations;
 ///
 /// assert_eq!(items, vec![1, 2, 4, 5]);
 /// ```
-pub fn edit<List>(items: &mut List, mut edit: impl FnMut(Slot<List>))
+pub fn edit<List>(items: &mut List, edit: impl FnMut(Slot<List>))
 where
     List: self::List + ?Sized,
 {
-    let mut index = 0;
+    edit_observed(items, &mut (), edit)
+}
 
-    while index < items.len() {
-        let mut stride = Stride(1);
-        edit(Slot::new(items, index, &mut stride));
-        index += stride.get();
+/// Like [`edit`], but reports each change made through a [`Slot`] to `observer`.
+///
+/// ```
+/// use editer::{edit_observed, observer::EditObserver};
+///
+/// #[derive(Default)]
+/// struct Changes {
+///     inserted: Vec<(usize, usize)>,
+///     removed: Vec<usize>,
+/// }
+///
+/// impl EditObserver for Changes {
+///     fn on_insert(&mut self, index: usize, count: usize) {
+///         self.inserted.push((index, count));
+///     }
+///
+///     fn on_remove(&mut self, index: usize) {
+///         self.removed.push(index);
+///     }
+/// }
+///
+/// let mut items = vec![1, 2, 3, 4, 5];
+/// let mut changes = Changes::default();
+///
+/// edit_observed(&mut items, &mut changes, |item| {
+///     if item == 2 {
+///         item.insert_after([6, 7]);
+///     } else if item == 4 {
+///         item.remove();
+///     }
+/// });
+///
+/// assert_eq!(items, vec![1, 2, 6, 7, 3, 5]);
+/// assert_eq!(changes.inserted, [(2, 2)]);
+/// assert_eq!(changes.removed, [5]);
+/// ```
+pub fn edit_observed<List, Observer>(
+    items: &mut List,
+    observer: &mut Observer,
+    mut edit: impl FnMut(Slot<List, Observer>),
+) where
+    List: self::List + ?Sized,
+    Observer: EditObserver + ?Sized,
+{
+    match run(items, observer, |slot| {
+        edit(slot);
+        Ok::<(), Infallible>(())
+    }) {
+        Ok(_) => {}
+        Err(never) => match never {},
+    }
+}
+
+/// Like [`edit`], but returns an [`EditSummary`] describing the changes made to `items`.
+///
+/// ```
+/// use editer::edit_with_summary;
+///
+/// let mut items = vec![1, 2, 3, 4, 5];
+///
+/// let summary = edit_with_summary(&mut items, |item| {
+///     if item == 2 {
+///         item.insert_after([6, 7]);
+///     } else if item == 4 {
+///         item.remove();
+///     }
+/// });
+///
+/// assert_eq!(summary.visited, 5);
+/// assert_eq!(summary.inserted, 2);
+/// assert_eq!(summary.removed, 1);
+/// assert_eq!(summary.final_len, 6);
+/// ```
+pub fn edit_with_summary<List>(items: &mut List, mut edit: impl FnMut(Slot<List>)) -> EditSummary
+where
+    List: self::List + ?Sized,
+{
+    match run(items, &mut (), |slot| {
+        edit(slot);
+        Ok::<(), Infallible>(())
+    }) {
+        Ok(summary) => summary,
+        Err(never) => match never {},
+    }
+}
+
+/// Moves the items in `items` for which `partition` returns `true` into `sink`, preserving their
+/// order. Keeps the rest.
+///
+/// `partition` is given a mutable reference to each item, so it can also modify the items it
+/// keeps.
+///
+/// ```
+/// use editer::edit_partition;
+/// use std::collections::VecDeque;
+///
+/// let mut items = vec![1, 2, 3, 4, 5];
+/// let mut evens = VecDeque::from([0]);
+///
+/// edit_partition(&mut items, &mut evens, |item| {
+///     if *item % 2 == 0 {
+///         true
+///     } else {
+///         *item *= 10;
+///         false
+///     }
+/// });
+///
+/// assert_eq!(items, vec![10, 30, 50]);
+/// assert_eq!(evens, VecDeque::from([0, 2, 4]));
+/// ```
+pub fn edit_partition<List>(
+    items: &mut List,
+    sink: &mut impl Extend<List::Item>,
+    mut partition: impl FnMut(&mut List::Item) -> bool,
+) where
+    List: self::List + ?Sized,
+{
+    edit(items, |mut item| {
+        if partition(&mut item) {
+            item.remove_into(sink);
+        }
+    })
+}
+
+/// Like [`edit`], but threads an accumulator through the calls to `fold`, starting with `init`,
+/// and returns its final value.
+///
+/// ```
+/// use editer::edit_fold;
+///
+/// let mut items = vec![1, 2, 3, 4, 5];
+///
+/// let sum_of_removed = edit_fold(&mut items, 0, |sum, item| {
+///     if *item % 2 == 0 {
+///         let sum = sum + *item;
+///         item.remove();
+///         sum
+///     } else {
+///         sum
+///     }
+/// });
+///
+/// assert_eq!(items, vec![1, 3, 5]);
+/// assert_eq!(sum_of_removed, 6);
+/// ```
+pub fn edit_fold<List, Acc>(
+    items: &mut List,
+    init: Acc,
+    mut fold: impl FnMut(Acc, Slot<List>) -> Acc,
+) -> Acc
+where
+    List: self::List + ?Sized,
+{
+    match try_edit_fold(items, init, |acc, slot| {
+        Ok::<Acc, Infallible>(fold(acc, slot))
+    }) {
+        Ok(acc) => acc,
+        Err(never) => match never {},
     }
 }
 
@@ -143,7 +396,8 @@ where
 /// Iterates over `items`, calling `edit` with a [`Slot`] for each item. The `Slot` allows
 /// accessing the current item and/or updating the list at the current position.
 ///
-/// Stops at the first error and returns it.
+/// Stops at the first error and returns it. Use [`try_edit_with_summary`] to also get an
+/// [`EditSummary`] back.
 ///
 /// ```
 /// use editer::try_edit;
@@ -164,17 +418,159 @@ where
 /// ```
 pub fn try_edit<List, Error>(
     items: &mut List,
-    mut edit: impl FnMut(Slot<List>) -> Result<(), Error>,
+    edit: impl FnMut(Slot<List>) -> Result<(), Error>,
+) -> Result<(), Error>
+where
+    List: self::List + ?Sized,
+{
+    try_edit_observed(items, &mut (), edit)
+}
+
+/// The fallible version of [`edit_with_summary`].
+///
+/// Like [`try_edit`], but returns an [`EditSummary`] describing the changes made to `items` if
+/// `edit` never fails.
+///
+/// This is a separate function so that `try_edit` keeps returning `Result<(), Error>`. Code that
+/// names that type, or compares the result with `Ok(())`, keeps compiling.
+///
+/// ```
+/// use editer::try_edit_with_summary;
+///
+/// let mut items = vec![1, 2, 3, 4, 5];
+///
+/// let summary = try_edit_with_summary(&mut items, |item| {
+///     if item == 4 {
+///         item.remove();
+///     }
+///
+///     Ok::<_, ()>(())
+/// })
+/// .unwrap();
+///
+/// assert_eq!(items, vec![1, 2, 3, 5]);
+/// assert_eq!(summary.removed, 1);
+/// ```
+pub fn try_edit_with_summary<List, Error>(
+    items: &mut List,
+    edit: impl FnMut(Slot<List>) -> Result<(), Error>,
+) -> Result<EditSummary, Error>
+where
+    List: self::List + ?Sized,
+{
+    run(items, &mut (), edit)
+}
+
+/// The fallible version of [`edit_observed`].
+///
+/// Like [`try_edit`], but reports each change made through a [`Slot`] to `observer`.
+pub fn try_edit_observed<List, Observer, Error>(
+    items: &mut List,
+    observer: &mut Observer,
+    edit: impl FnMut(Slot<List, Observer>) -> Result<(), Error>,
+) -> Result<(), Error>
+where
+    List: self::List + ?Sized,
+    Observer: EditObserver + ?Sized,
+{
+    run(items, observer, edit)?;
+    Ok(())
+}
+
+/// The fallible version of [`edit_fold`].
+///
+/// Like [`edit_fold`], but stops at the first error returned by `fold` and returns it.
+///
+/// ```
+/// use editer::try_edit_fold;
+///
+/// let mut items = vec!["1", "2", "x", "4"];
+///
+/// let result = try_edit_fold(&mut items, 0, |sum, item| {
+///     let number: i32 = item.parse().map_err(|_| *item)?;
+///     item.remove();
+///     Ok(sum + number)
+/// });
+///
+/// assert_eq!(result, Err("x"));
+/// assert_eq!(items, vec!["x", "4"]);
+/// ```
+pub fn try_edit_fold<List, Acc, Error>(
+    items: &mut List,
+    init: Acc,
+    mut fold: impl FnMut(Acc, Slot<List>) -> Result<Acc, Error>,
+) -> Result<Acc, Error>
+where
+    List: self::List + ?Sized,
+{
+    let mut acc = Some(init);
+
+    run(items, &mut (), |slot| {
+        // `acc` is only empty after `fold` has returned an error, which stops the edit.
+        let current = acc.take().expect("accumulator missing");
+        acc = Some(fold(current, slot)?);
+        Ok(())
+    })?;
+
+    Ok(acc.expect("accumulator missing"))
+}
+
+fn run<List, Observer, Error>(
+    items: &mut List,
+    observer: &mut Observer,
+    edit: impl FnMut(Slot<List, Observer>) -> Result<(), Error>,
+) -> Result<EditSummary, Error>
+where
+    List: self::List + ?Sized,
+    Observer: EditObserver + ?Sized,
+{
+    let mut stride = Stride::new();
+    walk(items, &mut stride, observer, edit)?;
+    Ok(stride.finish(items.len()))
+}
+
+fn walk<List, Observer, Error>(
+    items: &mut List,
+    stride: &mut Stride,
+    observer: &mut Observer,
+    mut edit: impl FnMut(Slot<List, Observer>) -> Result<(), Error>,
 ) -> Result<(), Error>
 where
     List: self::List + ?Sized,
+    Observer: EditObserver + ?Sized,
+{
+    drive(
+        items,
+        stride,
+        |items| items.len(),
+        |items, index, stride| edit(Slot::new(items, index, stride, observer)),
+    )
+}
+
+/// Calls `visit` with the index of each item in `items` that `stride` says should be visited,
+/// where `len` returns the current length of `items`.
+///
+/// This is the loop shared by [`walk`] and editors like `Zip`, whose items aren't
+/// stored in a single [`List`].
+pub(crate) fn drive<Items, Error>(
+    items: &mut Items,
+    stride: &mut Stride,
+    len: impl Fn(&Items) -> usize,
+    mut visit: impl FnMut(&mut Items, usize, &mut Stride) -> Result<(), Error>,
+) -> Result<(), Error>
+where
+    Items: ?Sized,
 {
     let mut index = 0;
 
-    while index < items.len() {
-        let mut stride = Stride(1);
-        edit(Slot::new(items, index, &mut stride))?;
-        index += stride.get();
+    while index < len(items) - stride.tail() {
+        if stride.skip(len(items) - index) {
+            index += 1;
+            continue;
+        }
+
+        visit(items, index, stride)?;
+        index += stride.advance();
     }
 
     Ok(())
@@ -246,17 +642,25 @@ pub trait List {
     fn index_mut(&mut self, index: usize) -> &mut Self::Item;
 
     /// Inserts `items` at `index`.
-    fn insert(&mut self, index: usize, items: impl Iterator<Item = Self::Item> + ExactSizeIterator);
+    fn insert(&mut self, index: usize, items: impl ExactSizeIterator<Item = Self::Item>);
 
-    /// Removes the item at `index`.
-    fn remove(&mut self, index: usize);
+    /// Removes and returns the item at `index`.
+    ///
+    /// `remove` returned nothing in editer 0.2 and earlier. Implementations written for those
+    /// versions need to return the removed item now, which [`Slot::remove_into`] and
+    /// [`List::move_item`] rely on.
+    fn remove(&mut self, index: usize) -> Self::Item;
+
+    /// Moves the item at `from` to `to`, shifting the items in between by one.
+    ///
+    /// The default implementation removes the item and inserts it again.
+    fn move_item(&mut self, from: usize, to: usize) {
+        let item = self.remove(from);
+        self.insert(to, iter::once(item));
+    }
 
     /// Replaces the item at `index` with the zero or more `items`.
-    fn replace(
-        &mut self,
-        index: usize,
-        mut items: impl Iterator<Item = Self::Item> + ExactSizeIterator,
-    ) {
+    fn replace(&mut self, index: usize, mut items: impl ExactSizeIterator<Item = Self::Item>) {
         if let Some(item) = items.next() {
             *self.index_mut(index) = item;
 
@@ -265,16 +669,320 @@ pub trait List {
             self.remove(index);
         }
     }
+
+    /// Replaces the items in `range` with the zero or more `items`.
+    fn replace_range(
+        &mut self,
+        range: Range<usize>,
+        mut items: impl ExactSizeIterator<Item = Self::Item>,
+    ) {
+        let mut index = range.start;
+
+        while index < range.end {
+            match items.next() {
+                Some(item) => *self.index_mut(index) = item,
+                None => break,
+            }
+
+            index += 1;
+        }
+
+        for _ in index..range.end {
+            self.remove(index);
+        }
+
+        self.insert(index, items);
+    }
 }
 
-struct Stride(usize);
+struct Stride {
+    value: usize,
+    summary: EditSummary,
+
+    /// The number of items moved to the end of the list, which won't be visited.
+    tail: usize,
+
+    skips: Skips,
+
+    /// The number of items pushed to the front of the list, which shift the current position.
+    shift: usize,
+
+    changes: ChangeLog,
+}
 
 impl Stride {
+    pub fn new() -> Stride {
+        Stride {
+            value: 1,
+            summary: EditSummary::default(),
+            tail: 0,
+            skips: Skips::new(),
+            shift: 0,
+            changes: ChangeLog::new(),
+        }
+    }
+
+    /// Starts keeping a log of the changes made through [`Slot`]s, for
+    /// [`edit_with_index_map`](index_map::edit_with_index_map).
+    #[cfg(feature = "alloc")]
+    pub fn record_changes(&mut self) {
+        self.changes.0 = Some(alloc::vec::Vec::new());
+    }
+
+    /// Returns the changes recorded since [`record_changes`](Stride::record_changes), in the
+    /// order they were made.
+    #[cfg(feature = "alloc")]
+    pub fn take_changes(&mut self) -> alloc::vec::Vec<(usize, Change)> {
+        self.changes.0.take().unwrap_or_default()
+    }
+
+    /// Records that `change` was made through the [`Slot`] at `index`, if changes are being
+    /// recorded.
+    pub fn record(&mut self, index: usize, change: Change) {
+        self.changes.record(index, change)
+    }
+
     pub fn set(&mut self, value: usize) {
-        self.0 = value
+        self.value = value
+    }
+
+    pub fn summary(&mut self) -> &mut EditSummary {
+        &mut self.summary
     }
 
-    pub fn get(self) -> usize {
-        self.0
+    pub fn tail(&self) -> usize {
+        self.tail
     }
+
+    /// Records that the current item has been moved towards the start of the list.
+    pub fn moved_back(&mut self) {
+        // An item that's already been visited has shifted into the current position, just like an
+        // item pushed to the front.
+        self.summary.moved += 1;
+        self.value = 0;
+        self.shift += 1;
+    }
+
+    /// Records that the item at `from` has been moved to the end of a list of length `len`.
+    pub fn moved_to_end(&mut self, len: usize, from: usize) {
+        self.moved_forward(len, from, len - 1);
+        self.tail += 1;
+    }
+
+    /// Records that the item at `from` has been moved to `to` in a list of length `len`.
+    #[cfg(feature = "alloc")]
+    pub fn moved(&mut self, len: usize, from: usize, to: usize) {
+        if to < from {
+            self.moved_back();
+        } else if to + 1 >= len - self.tail {
+            // An item moved right up against the items already moved to the end joins them.
+            self.moved_forward(len, from, to);
+            self.tail += 1;
+        } else {
+            self.moved_forward(len, from, to);
+            self.skips.defer(len - to);
+        }
+    }
+
+    fn moved_forward(&mut self, len: usize, from: usize, to: usize) {
+        // The next item has taken the place of the moved one.
+        self.summary.moved += 1;
+        self.value = 0;
+        self.skips.moved(len, from, to);
+    }
+
+    /// Records that `count` items have been pushed to the front of the list.
+    pub fn pushed_front(&mut self, count: usize) {
+        self.shift += count;
+    }
+
+    /// Records that `count` items have been inserted just before the items moved to the end of
+    /// the list.
+    pub fn pushed_back(&mut self, count: usize) {
+        self.skips.pushed_back(count);
+    }
+
+    /// Returns `true`, and forgets the skip, if the item at `distance` from the end of the list
+    /// was moved there and shouldn't be visited.
+    pub fn skip(&mut self, distance: usize) -> bool {
+        self.skips.skip(distance)
+    }
+
+    /// Returns the position of the item `count` items past `index` in a list of length `len`,
+    /// not counting items that shouldn't be visited, and forgets the skips passed over.
+    pub fn seek(&mut self, len: usize, index: usize, count: usize) -> usize {
+        self.skips.seek(len, index, count)
+    }
+
+    /// Forgets the skips before `position` in a list of length `len`, then returns `true`, and
+    /// forgets the skip, if the item at `position` shouldn't be visited.
+    pub fn skip_until(&mut self, len: usize, position: usize) -> bool {
+        self.skips.forget_before(len, position);
+        self.skips.skip(len - position)
+    }
+
+    /// Returns the number of items produced at the current position and the number of items
+    /// that shifted into place before them, and starts afresh as if at a new position.
+    pub fn take_span(&mut self) -> (usize, usize) {
+        (
+            core::mem::replace(&mut self.value, 1),
+            core::mem::take(&mut self.shift),
+        )
+    }
+
+    /// Undoes [`take_span`](Stride::take_span), once all the items produced at a position have
+    /// been accounted for.
+    pub fn restore_span(&mut self, value: usize, shift: usize) {
+        self.value = value;
+        self.shift = shift;
+    }
+
+    pub fn advance(&mut self) -> usize {
+        self.summary.visited += 1;
+        core::mem::replace(&mut self.value, 1) + core::mem::take(&mut self.shift)
+    }
+
+    pub fn finish(self, final_len: usize) -> EditSummary {
+        EditSummary {
+            final_len,
+            ..self.summary
+        }
+    }
+}
+
+/// The distances from the end of the list of items moved forward into the part of the list that's
+/// yet to be visited, nearest to the current position last.
+#[cfg(feature = "alloc")]
+struct Skips(alloc::vec::Vec<usize>);
+
+#[cfg(feature = "alloc")]
+impl Skips {
+    fn new() -> Skips {
+        Skips(alloc::vec::Vec::new())
+    }
+
+    /// Shifts the items between `from` and `to`, which the item at `from` has been moved forward
+    /// past, towards the start of a list of length `len` by one.
+    fn moved(&mut self, len: usize, from: usize, to: usize) {
+        for distance in &mut self.0 {
+            if len - to <= *distance && *distance < len - from {
+                *distance += 1;
+            }
+        }
+    }
+
+    fn pushed_back(&mut self, count: usize) {
+        for distance in &mut self.0 {
+            *distance += count;
+        }
+    }
+
+    fn defer(&mut self, distance: usize) {
+        let position = self.0.partition_point(|&other| other < distance);
+        self.0.insert(position, distance);
+    }
+
+    fn skip(&mut self, distance: usize) -> bool {
+        if self.0.last() == Some(&distance) {
+            self.0.pop();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn seek(&mut self, len: usize, mut index: usize, mut count: usize) -> usize {
+        while let Some(&distance) = self.0.last() {
+            let position = len - distance;
+
+            if position > index + count {
+                break;
+            }
+
+            count -= position - index;
+            index = position + 1;
+            self.0.pop();
+        }
+
+        index + count
+    }
+
+    fn forget_before(&mut self, len: usize, position: usize) {
+        while let Some(&distance) = self.0.last() {
+            if len - distance >= position {
+                break;
+            }
+
+            self.0.pop();
+        }
+    }
+}
+
+/// Without `alloc` there's no [`Slot::move_by`], and the only way to move an item forward is to
+/// move it to the end of the list, where it isn't visited anyway. So there's never an item to
+/// skip.
+#[cfg(not(feature = "alloc"))]
+struct Skips;
+
+#[cfg(not(feature = "alloc"))]
+impl Skips {
+    fn new() -> Skips {
+        Skips
+    }
+
+    fn moved(&mut self, _: usize, _: usize, _: usize) {}
+
+    fn pushed_back(&mut self, _: usize) {}
+
+    fn skip(&mut self, _: usize) -> bool {
+        false
+    }
+
+    fn seek(&mut self, _: usize, index: usize, count: usize) -> usize {
+        index + count
+    }
+
+    fn forget_before(&mut self, _: usize, _: usize) {}
+}
+
+/// A change made through a [`Slot`], relative to the slot's position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Change {
+    InsertedBefore(usize),
+    InsertedAfter(usize),
+    Replaced(usize),
+    Removed,
+    PushedFront(usize),
+    PushedBack(usize),
+    Moved(usize),
+}
+
+#[cfg(feature = "alloc")]
+struct ChangeLog(Option<alloc::vec::Vec<(usize, Change)>>);
+
+#[cfg(feature = "alloc")]
+impl ChangeLog {
+    fn new() -> ChangeLog {
+        ChangeLog(None)
+    }
+
+    fn record(&mut self, index: usize, change: Change) {
+        if let Some(changes) = &mut self.0 {
+            changes.push((index, change));
+        }
+    }
+}
+
+/// Without `alloc` there's no [`edit_with_index_map`](index_map::edit_with_index_map) to record
+/// changes for.
+#[cfg(not(feature = "alloc"))]
+struct ChangeLog;
+
+#[cfg(not(feature = "alloc"))]
+impl ChangeLog {
+    fn new() -> ChangeLog {
+        ChangeLog
+    }
+
+    fn record(&mut self, _: usize, _: Change) {}
 }
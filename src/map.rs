@@ -0,0 +1,357 @@
+//! Editing a [`Vec`] while changing the type of its items.
+
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+/// Iterates over `items`, calling `edit` with a [`MapSlot`] for each item. The `MapSlot` takes
+/// ownership of the item and emits zero or more items of another type in its place.
+///
+/// When `A` and `B` have the same size and alignment, the buffer of `items` is reused for the
+/// result. Otherwise, the result is collected into a new buffer.
+///
+/// ```
+/// use editer::map::edit_map;
+///
+/// let lines = vec!["1,2", "", "3"];
+///
+/// let numbers: Vec<u64> = edit_map(lines, |line| {
+///     line.replace_with(|line| line.split(',').filter_map(|number| number.parse().ok()));
+/// });
+///
+/// assert_eq!(numbers, vec![1, 2, 3]);
+/// ```
+pub fn edit_map<A, B>(items: Vec<A>, mut edit: impl FnMut(MapSlot<A, B>)) -> Vec<B> {
+    if mem::size_of::<A>() != mem::size_of::<B>()
+        || mem::align_of::<A>() != mem::align_of::<B>()
+        || mem::size_of::<A>() == 0
+    {
+        let mut output = VecDeque::with_capacity(items.len());
+
+        for item in items {
+            edit(MapSlot::new(item, &mut output));
+        }
+
+        return Vec::from(output);
+    }
+
+    let mut items = ManuallyDrop::new(items);
+
+    let mut buffer = Buffer::<A, B> {
+        pointer: items.as_mut_ptr(),
+        len: items.len(),
+        capacity: items.capacity(),
+        read: 0,
+        written: 0,
+        marker: PhantomData,
+    };
+
+    // Items emitted while there's no vacant space in the buffer wait here until there is.
+    let mut pending = VecDeque::new();
+
+    while buffer.read < buffer.len {
+        // SAFETY: `read` is in bounds, and the item there is never read again.
+        let item = unsafe { ptr::read(buffer.pointer.add(buffer.read)) };
+        buffer.read += 1;
+
+        edit(MapSlot::new(item, &mut pending));
+
+        while buffer.written < buffer.read {
+            match pending.pop_front() {
+                // SAFETY: the item previously at `written` has been read, so the space is vacant,
+                // and `A` and `B` have the same layout.
+                Some(item) => unsafe {
+                    ptr::write(buffer.pointer.add(buffer.written) as *mut B, item);
+                    buffer.written += 1;
+                },
+
+                None => break,
+            }
+        }
+    }
+
+    let buffer = ManuallyDrop::new(buffer);
+
+    // SAFETY: the first `written` items in the buffer have been replaced with `B`s, and the
+    // remaining `A`s have all been read.
+    let mut output =
+        unsafe { Vec::from_raw_parts(buffer.pointer as *mut B, buffer.written, buffer.capacity) };
+
+    output.extend(pending);
+    output
+}
+
+/// The buffer of a [`Vec`] being edited by [`edit_map`], partly filled with `B`s and partly with
+/// `A`s.
+///
+/// Dropping a `Buffer` drops the `B`s written to it and the `A`s yet to be read from it, and frees
+/// it. This only happens if `edit` panics.
+struct Buffer<A, B> {
+    pointer: *mut A,
+    len: usize,
+    capacity: usize,
+    read: usize,
+    written: usize,
+    marker: PhantomData<B>,
+}
+
+impl<A, B> Drop for Buffer<A, B> {
+    fn drop(&mut self) {
+        // SAFETY: the items from `read` onwards are unread `A`s, the first `written` items are
+        // `B`s, and `A` and `B` have the same layout, so the buffer can be freed as a `Vec<B>`.
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.pointer.add(self.read),
+                self.len - self.read,
+            ));
+
+            drop(Vec::from_raw_parts(
+                self.pointer as *mut B,
+                self.written,
+                self.capacity,
+            ));
+        }
+    }
+}
+
+/// Owns the current item in an [`edit_map`], and emits the items that take its place.
+///
+/// If a `MapSlot` is dropped without calling any of its methods that take `self`, its item is
+/// dropped and nothing is emitted in its place.
+pub struct MapSlot<'output, A, B> {
+    item: A,
+    output: &'output mut VecDeque<B>,
+}
+
+impl<'output, A, B> MapSlot<'output, A, B> {
+    fn new(item: A, output: &'output mut VecDeque<B>) -> MapSlot<'output, A, B> {
+        MapSlot { item, output }
+    }
+
+    /// Returns a shared reference to the current item.
+    pub fn get(&self) -> &A {
+        &self.item
+    }
+
+    /// Returns a mutable reference to the current item.
+    pub fn get_mut(&mut self) -> &mut A {
+        &mut self.item
+    }
+
+    /// Emits a single item, built from the current item by `map`.
+    ///
+    /// ```
+    /// use editer::map::edit_map;
+    ///
+    /// let numbers = edit_map(vec![1u32, 2, 3], |number| number.map(|number| number as f32));
+    ///
+    /// assert_eq!(numbers, vec![1.0, 2.0, 3.0]);
+    /// ```
+    pub fn map(self, map: impl FnOnce(A) -> B) {
+        self.output.push_back(map(self.item));
+    }
+
+    /// Drops the current item and emits zero or more `items` in its place.
+    ///
+    /// ```
+    /// use editer::map::edit_map;
+    ///
+    /// let words = edit_map(vec![1, 2, 3], |number| {
+    ///     if number == 2 {
+    ///         number.replace(["two", "deux"]);
+    ///     } else {
+    ///         number.replace(["?"]);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(words, vec!["?", "two", "deux", "?"]);
+    /// ```
+    pub fn replace(self, items: impl IntoIterator<Item = B>) {
+        self.output.extend(items);
+    }
+
+    /// Emits zero or more items, built from the current item by `build`.
+    ///
+    /// ```
+    /// use editer::map::edit_map;
+    ///
+    /// let words = edit_map(vec![String::from("a b"), String::from("c")], |line| {
+    ///     line.replace_with(|line| {
+    ///         line.split(' ').map(String::from).collect::<Vec<_>>()
+    ///     });
+    /// });
+    ///
+    /// assert_eq!(words, vec!["a", "b", "c"]);
+    /// ```
+    pub fn replace_with<Items>(self, build: impl FnOnce(A) -> Items)
+    where
+        Items: IntoIterator<Item = B>,
+    {
+        self.output.extend(build(self.item));
+    }
+
+    /// Drops the current item without emitting anything in its place.
+    ///
+    /// ```
+    /// use editer::map::edit_map;
+    ///
+    /// let numbers = edit_map(vec![1, 2, 3], |number| {
+    ///     if number == 2 {
+    ///         number.remove();
+    ///     } else {
+    ///         number.map(|number| number * 10);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(numbers, vec![10, 30]);
+    /// ```
+    pub fn remove(self) {}
+}
+
+impl<'output, A, B> Deref for MapSlot<'output, A, B> {
+    type Target = A;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<'output, A, B> DerefMut for MapSlot<'output, A, B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.get_mut()
+    }
+}
+
+impl<'output, A, B> PartialEq<A> for MapSlot<'output, A, B>
+where
+    A: PartialEq,
+{
+    fn eq(&self, other: &A) -> bool {
+        self.get() == other
+    }
+}
+
+impl<'output, A, B> Display for MapSlot<'output, A, B>
+where
+    A: Display,
+{
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.get().fmt(formatter)
+    }
+}
+
+impl<'output, A, B> Debug for MapSlot<'output, A, B>
+where
+    A: Debug,
+{
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.get().fmt(formatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edit_map;
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    #[test]
+    fn reusing_the_buffer_for_items_of_the_same_layout() {
+        let items: Vec<u32> = (1..=5).collect();
+        let pointer = items.as_ptr() as usize;
+
+        let mapped = edit_map(items, |item| item.map(|item| -(item as i32)));
+
+        assert_eq!(mapped, vec![-1, -2, -3, -4, -5]);
+        assert_eq!(mapped.as_ptr() as usize, pointer);
+    }
+
+    #[test]
+    fn expanding_items_beyond_the_original_length() {
+        let items: Vec<u64> = vec![3, 1];
+
+        let mapped = edit_map(items, |item| {
+            let count = *item as usize;
+            item.replace_with(|item| vec![item as i64; count]);
+        });
+
+        assert_eq!(mapped, vec![3, 3, 3, 1]);
+    }
+
+    #[test]
+    fn expanding_and_shrinking_items_in_place() {
+        let items: Vec<u64> = vec![1, 2, 0, 3, 0, 0];
+
+        let mapped: Vec<i64> = edit_map(items, |item| {
+            let count = *item as usize;
+            item.replace_with(|item| vec![item as i64; count]);
+        });
+
+        assert_eq!(mapped, vec![1, 2, 2, 3, 3, 3]);
+    }
+
+    #[test]
+    fn mapping_to_items_of_a_different_layout() {
+        let items = vec![1u8, 2, 3];
+
+        let mapped = edit_map(items, |item| {
+            if item == 2 {
+                item.remove();
+            } else {
+                item.replace_with(|item| [u64::from(item); 2]);
+            }
+        });
+
+        assert_eq!(mapped, vec![1, 1, 3, 3]);
+    }
+
+    #[test]
+    fn mapping_zero_sized_items() {
+        let mapped = edit_map(vec![(), (), ()], |item| item.replace([(), ()]));
+
+        assert_eq!(mapped.len(), 6);
+    }
+
+    #[test]
+    fn dropping_unconsumed_slots() {
+        let counter = Rc::new(());
+        let items = vec![counter.clone(), counter.clone()];
+
+        let mapped: Vec<Rc<()>> = edit_map(items, |_| {});
+
+        assert!(mapped.is_empty());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn dropping_every_item_when_the_editor_panics() {
+        let counter = Rc::new(());
+        let items = vec![counter.clone(); 5];
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut visited = 0;
+
+            edit_map(items, |item: super::MapSlot<Rc<()>, Rc<()>>| {
+                visited += 1;
+
+                if visited == 4 {
+                    panic!("Whoops!");
+                } else if visited == 1 {
+                    item.replace_with(|item| vec![item.clone(), item.clone(), item]);
+                } else {
+                    item.remove();
+                }
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,180 @@
+//! Editing only the items in a list that match a predicate, without visiting the rest.
+
+use crate::{slot::Slot, List, Stride};
+
+/// Calls `edit` with a [`Slot`] for each item in `items` for which `predicate` returns `true`.
+/// Other items are passed over without constructing a `Slot` for them.
+///
+/// As with [`edit`](crate::edit), items inserted or used as replacements aren't considered, and
+/// items moved elsewhere in the list aren't considered again.
+///
+/// ```
+/// use editer::matching::edit_where;
+///
+/// let mut words = vec!["one", "", "two", "", "three"];
+///
+/// edit_where(&mut words, |word| word.is_empty(), |word| word.remove());
+///
+/// assert_eq!(words, vec!["one", "two", "three"]);
+/// ```
+pub fn edit_where<List>(
+    items: &mut List,
+    mut predicate: impl FnMut(&List::Item) -> bool,
+    edit: impl FnMut(Slot<List>),
+) where
+    List: self::List + ?Sized,
+{
+    edit_found(
+        items,
+        |items, start, end| (start..end).find(|&index| predicate(items.index(index))),
+        edit,
+    )
+}
+
+/// Like [`edit_where`], but visits only the bytes in `items` equal to `byte`.
+///
+/// This is a separate function rather than a fast path inside `edit_where` because stable Rust
+/// can't specialize `edit_where` for lists of bytes, and because the search needs the list's
+/// bytes as one contiguous slice, via [`AsRef<[u8]>`](AsRef). Searching that slice directly
+/// avoids calling a predicate for each item.
+///
+/// With the **`memchr`** feature enabled, the search uses [`memchr`]. Editer enables it without
+/// memchr's default `std` feature, so memchr can't detect CPU features at runtime: it uses SSE2
+/// on x86_64 and a word-at-a-time search on most other targets, rather than AVX2. That's still
+/// faster than comparing one byte at a time, which is what happens without the feature.
+///
+/// ```
+/// use editer::matching::edit_where_byte;
+///
+/// let mut text = b"a\r\nb\r\nc".to_vec();
+///
+/// edit_where_byte(&mut text, b'\r', |byte| byte.remove());
+///
+/// assert_eq!(text, b"a\nb\nc");
+/// ```
+///
+/// [`memchr`]: https://docs.rs/memchr/latest/memchr/fn.memchr.html
+pub fn edit_where_byte<List>(items: &mut List, byte: u8, edit: impl FnMut(Slot<List>))
+where
+    List: self::List<Item = u8> + AsRef<[u8]> + ?Sized,
+{
+    edit_found(
+        items,
+        |items, start, end| {
+            let bytes = &items.as_ref()[start..end];
+
+            #[cfg(feature = "memchr")]
+            let found = memchr::memchr(byte, bytes);
+
+            #[cfg(not(feature = "memchr"))]
+            let found = bytes.iter().position(|&other| other == byte);
+
+            found.map(|offset| start + offset)
+        },
+        edit,
+    )
+}
+
+/// Calls `edit` with a [`Slot`] for each position in `items` returned by `find`, which returns
+/// the first position from `start` up to `end` to visit.
+fn edit_found<List>(
+    items: &mut List,
+    mut find: impl FnMut(&List, usize, usize) -> Option<usize>,
+    mut edit: impl FnMut(Slot<List>),
+) where
+    List: self::List + ?Sized,
+{
+    let mut stride = Stride::new();
+    let mut index = 0;
+
+    while index < items.len() - stride.tail() {
+        let position = match find(items, index, items.len() - stride.tail()) {
+            Some(position) => position,
+            None => break,
+        };
+
+        if stride.skip_until(items.len(), position) {
+            index = position + 1;
+            continue;
+        }
+
+        edit(Slot::new(items, position, &mut stride, &mut ()));
+        index = position + stride.advance();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_where, edit_where_byte};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn visiting_only_matching_items() {
+        let mut items = VecDeque::from([1, 2, 3, 4, 5, 6]);
+        let mut visited = Vec::new();
+
+        edit_where(&mut items, |item| item % 3 == 0, |item| visited.push(*item));
+
+        assert_eq!(visited, [3, 6]);
+    }
+
+    #[test]
+    fn not_considering_inserted_items() {
+        let mut items = vec![1, 2, 1];
+        let mut visited = Vec::new();
+
+        edit_where(
+            &mut items,
+            |item| *item < 3,
+            |item| {
+                visited.push(*item);
+
+                if item == 1 {
+                    item.insert_after([0, 5]);
+                } else if item == 2 {
+                    item.replace([7, 0]);
+                }
+            },
+        );
+
+        assert_eq!(visited, [1, 2, 1]);
+        assert_eq!(items, vec![1, 0, 5, 7, 0, 1, 0, 5]);
+    }
+
+    #[test]
+    fn not_revisiting_moved_items() {
+        let mut items = vec![1, 2, 3, 4, 5];
+        let mut visited = Vec::new();
+
+        edit_where(
+            &mut items,
+            |item| item % 2 == 1,
+            |item| {
+                visited.push(*item);
+
+                if item == 1 {
+                    item.move_by(2);
+                } else if item == 3 {
+                    item.move_to_end();
+                }
+            },
+        );
+
+        assert_eq!(visited, [1, 3, 5]);
+        assert_eq!(items, vec![2, 1, 4, 5, 3]);
+    }
+
+    #[test]
+    fn editing_matching_bytes() {
+        let mut bytes = b"a,b,,c".to_vec();
+        let mut visited = 0;
+
+        edit_where_byte(&mut bytes, b',', |byte| {
+            visited += 1;
+            byte.replace(*b", ");
+        });
+
+        assert_eq!(visited, 3);
+        assert_eq!(bytes, b"a, b, , c");
+    }
+}
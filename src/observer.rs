@@ -0,0 +1,215 @@
+//! Observing the changes made to a list while editing it.
+
+/// Receives notifications of changes made to a list through a [`Slot`](crate::slot::Slot).
+///
+/// Each method is called after the corresponding change has been made, with indices that refer
+/// to the list as it is after the change. The default implementations do nothing, so observers
+/// only need to implement the methods for the changes they're interested in.
+///
+/// Mutating the current item in place (e.g. via [`DerefMut`](core::ops::DerefMut)) doesn't change
+/// the structure of the list and isn't reported.
+///
+/// `EditObserver` is implemented for `()`, which ignores all changes, for mutable references to
+/// observers, and for tuples of observers, which notify each of their elements in order.
+pub trait EditObserver {
+    /// Called when `count` items are inserted at `index`. `count` may be zero.
+    fn on_insert(&mut self, index: usize, count: usize) {
+        let _ = (index, count);
+    }
+
+    /// Called when the item at `index` is removed.
+    fn on_remove(&mut self, index: usize) {
+        let _ = index;
+    }
+
+    /// Called when the item at `index` is replaced with `count` items. `count` may be zero, in
+    /// which case the item was effectively removed.
+    fn on_replace(&mut self, index: usize, count: usize) {
+        let _ = (index, count);
+    }
+
+    /// Called when the item at `from` is moved to `to`, shifting the items in between.
+    ///
+    /// By default, a move is reported as a removal at `from` followed by an insertion of one
+    /// item at `to`.
+    fn on_move(&mut self, from: usize, to: usize) {
+        self.on_remove(from);
+        self.on_insert(to, 1);
+    }
+}
+
+impl EditObserver for () {}
+
+impl<Observer> EditObserver for &mut Observer
+where
+    Observer: EditObserver + ?Sized,
+{
+    fn on_insert(&mut self, index: usize, count: usize) {
+        (**self).on_insert(index, count)
+    }
+
+    fn on_remove(&mut self, index: usize) {
+        (**self).on_remove(index)
+    }
+
+    fn on_replace(&mut self, index: usize, count: usize) {
+        (**self).on_replace(index, count)
+    }
+
+    fn on_move(&mut self, from: usize, to: usize) {
+        (**self).on_move(from, to)
+    }
+}
+
+macro_rules! tuple_impls {
+    ($($name:ident)+) => {
+        impl<$($name),+> EditObserver for ($($name,)+)
+        where
+            $($name: EditObserver,)+
+        {
+            #[allow(non_snake_case)]
+            fn on_insert(&mut self, index: usize, count: usize) {
+                let ($($name,)+) = self;
+                $($name.on_insert(index, count);)+
+            }
+
+            #[allow(non_snake_case)]
+            fn on_remove(&mut self, index: usize) {
+                let ($($name,)+) = self;
+                $($name.on_remove(index);)+
+            }
+
+            #[allow(non_snake_case)]
+            fn on_replace(&mut self, index: usize, count: usize) {
+                let ($($name,)+) = self;
+                $($name.on_replace(index, count);)+
+            }
+
+            #[allow(non_snake_case)]
+            fn on_move(&mut self, from: usize, to: usize) {
+                let ($($name,)+) = self;
+                $($name.on_move(from, to);)+
+            }
+        }
+    };
+}
+
+tuple_impls!(A);
+tuple_impls!(A B);
+tuple_impls!(A B C);
+tuple_impls!(A B C D);
+
+#[cfg(test)]
+mod tests {
+    use super::EditObserver;
+    use crate::edit_observed;
+
+    #[derive(Default)]
+    struct Log(Vec<String>);
+
+    impl EditObserver for Log {
+        fn on_insert(&mut self, index: usize, count: usize) {
+            self.0.push(format!("insert {} at {}", count, index));
+        }
+
+        fn on_remove(&mut self, index: usize) {
+            self.0.push(format!("remove {}", index));
+        }
+
+        fn on_replace(&mut self, index: usize, count: usize) {
+            self.0.push(format!("replace {} with {}", index, count));
+        }
+
+        fn on_move(&mut self, from: usize, to: usize) {
+            self.0.push(format!("move {} to {}", from, to));
+        }
+    }
+
+    #[test]
+    fn observing_every_kind_of_change() {
+        let mut items = vec![1, 2, 3, 4, 5];
+        let mut log = Log::default();
+
+        edit_observed(&mut items, &mut log, |mut item| {
+            if item == 1 {
+                item.insert_before([6, 7]);
+            } else if item == 2 {
+                item.insert_after([8]);
+            } else if item == 3 {
+                *item = 9;
+            } else if item == 4 {
+                item.replace([10, 11, 12]);
+            } else if item == 5 {
+                item.remove();
+            }
+        });
+
+        assert_eq!(items, vec![6, 7, 1, 2, 8, 9, 10, 11, 12]);
+        assert_eq!(
+            log.0,
+            [
+                "insert 2 at 0",
+                "insert 1 at 4",
+                "replace 6 with 3",
+                "remove 9"
+            ]
+        );
+    }
+
+    #[test]
+    fn observing_with_a_tuple_of_observers() {
+        let mut items = vec![1, 2, 3];
+        let mut observers = (Log::default(), Log::default());
+
+        edit_observed(&mut items, &mut observers, |item| {
+            if item == 2 {
+                item.replace([]);
+            }
+        });
+
+        assert_eq!(items, vec![1, 3]);
+        assert_eq!(observers.0 .0, ["replace 1 with 0"]);
+        assert_eq!(observers.1 .0, ["replace 1 with 0"]);
+    }
+
+    #[test]
+    fn observing_moves() {
+        let mut items = vec![1, 2, 3, 4, 5];
+        let mut log = Log::default();
+
+        edit_observed(&mut items, &mut log, |item| {
+            if item == 2 {
+                item.move_by(2);
+            } else if item == 3 {
+                item.move_to_end();
+            } else if item == 5 {
+                item.move_to_front();
+            }
+        });
+
+        assert_eq!(items, vec![5, 1, 4, 2, 3]);
+        assert_eq!(log.0, ["move 1 to 3", "move 1 to 4", "move 3 to 0"]);
+    }
+
+    #[test]
+    fn reporting_moves_as_removals_and_insertions_by_default() {
+        #[derive(Default)]
+        struct Log(Vec<String>);
+
+        impl EditObserver for Log {
+            fn on_insert(&mut self, index: usize, count: usize) {
+                self.0.push(format!("insert {} at {}", count, index));
+            }
+
+            fn on_remove(&mut self, index: usize) {
+                self.0.push(format!("remove {}", index));
+            }
+        }
+
+        let mut log = Log::default();
+
+        log.on_move(3, 1);
+
+        assert_eq!(log.0, ["remove 3", "insert 1 at 1"]);
+    }
+}
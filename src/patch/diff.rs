@@ -0,0 +1,442 @@
+use super::{Operation, Patch};
+use crate::List;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Computes a minimal [`Patch`] that turns `old` into `new`.
+///
+/// Items that appear in both lists, in the same relative order, are left alone: the patch only
+/// removes items that aren't in `new` and inserts items that aren't in `old`, using the shortest
+/// edit script found by Myers' diff algorithm.
+///
+/// ```
+/// use editer::patch::{diff, Operation};
+///
+/// let mut items = vec![1, 2, 3, 4, 5];
+///
+/// let patch = diff(&items, &vec![1, 3, 4, 6, 5]);
+///
+/// assert_eq!(
+///     patch.operations(),
+///     [
+///         Operation::Remove { index: 1 },
+///         Operation::Insert { index: 3, items: vec![6] },
+///     ]
+/// );
+///
+/// patch.apply(&mut items).unwrap();
+///
+/// assert_eq!(items, vec![1, 3, 4, 6, 5]);
+/// ```
+pub fn diff<Old, New>(old: &Old, new: &New) -> Patch<Old::Item>
+where
+    Old: List + ?Sized,
+    New: List<Item = Old::Item> + ?Sized,
+    Old::Item: PartialEq + Clone,
+{
+    let (old_len, new_len) = (old.len(), new.len());
+
+    let prefix = (0..old_len.min(new_len))
+        .take_while(|&index| old.index(index) == new.index(index))
+        .count();
+
+    let suffix = (0..old_len.min(new_len) - prefix)
+        .take_while(|&offset| old.index(old_len - offset - 1) == new.index(new_len - offset - 1))
+        .count();
+
+    let steps = shortest_edit(
+        |x, y| old.index(prefix + x) == new.index(prefix + y),
+        old_len - prefix - suffix,
+        new_len - prefix - suffix,
+    );
+
+    let mut patch = Patch::new();
+    let mut index = prefix;
+    let mut steps = steps.into_iter().peekable();
+
+    while let Some(step) = steps.next() {
+        if let Step::Keep = step {
+            index += 1;
+            continue;
+        }
+
+        let mut removed = 0;
+        let mut inserted = Vec::new();
+
+        for step in core::iter::once(step).chain(core::iter::from_fn(|| {
+            steps.next_if(|step| !matches!(step, Step::Keep))
+        })) {
+            match step {
+                Step::Delete => removed += 1,
+                Step::Insert(y) => inserted.push(new.index(prefix + y).clone()),
+                Step::Keep => unreachable!(),
+            }
+        }
+
+        let replaced = if removed > 0 && !inserted.is_empty() {
+            1
+        } else {
+            0
+        };
+
+        for _ in replaced..removed {
+            patch.push(Operation::Remove { index });
+        }
+
+        if !inserted.is_empty() {
+            let count = inserted.len();
+
+            patch.push(if replaced == 1 {
+                Operation::Replace {
+                    index,
+                    items: inserted,
+                }
+            } else {
+                Operation::Insert {
+                    index,
+                    items: inserted,
+                }
+            });
+
+            index += count;
+        }
+    }
+
+    patch
+}
+
+enum Step {
+    Keep,
+    Delete,
+    Insert(usize),
+}
+
+// Finds the shortest sequence of steps that turns a list of length `n` into a list of length `m`,
+// given a function that compares the item at index `x` in the former to the item at index `y` in
+// the latter. See Eugene W. Myers, “An O(ND) Difference Algorithm and Its Variations” (1986).
+//
+// Uses the linear space refinement from section 4b of the paper: rather than keeping every
+// frontier around to backtrack through, it finds the middle snake of a shortest path and then
+// solves the parts before and after it on their own.
+fn shortest_edit(eq: impl Fn(usize, usize) -> bool, n: usize, m: usize) -> Vec<Step> {
+    let offset = (n + m + 1) / 2 + 1;
+
+    let mut steps = Vec::with_capacity(n.max(m));
+    let mut frontiers = Frontiers {
+        forward: alloc::vec![0; 2 * offset + 1],
+        backward: alloc::vec![0; 2 * offset + 1],
+    };
+
+    conquer(&eq, 0..n, 0..m, &mut frontiers, &mut steps);
+    steps
+}
+
+// For each number of non-diagonal moves d, the furthest x reached on each diagonal k = x − y,
+// counting from the start of the lists for the forward search and from the end for the backward
+// one. Shared by every part of the search, which never needs more than the first.
+struct Frontiers {
+    forward: Vec<usize>,
+    backward: Vec<usize>,
+}
+
+fn conquer(
+    eq: &impl Fn(usize, usize) -> bool,
+    mut old: Range<usize>,
+    mut new: Range<usize>,
+    frontiers: &mut Frontiers,
+    steps: &mut Vec<Step>,
+) {
+    while !old.is_empty() && !new.is_empty() && eq(old.start, new.start) {
+        steps.push(Step::Keep);
+        old.start += 1;
+        new.start += 1;
+    }
+
+    let mut suffix = 0;
+
+    while !old.is_empty() && !new.is_empty() && eq(old.end - 1, new.end - 1) {
+        suffix += 1;
+        old.end -= 1;
+        new.end -= 1;
+    }
+
+    if old.is_empty() {
+        steps.extend(new.map(Step::Insert));
+    } else if new.is_empty() {
+        steps.extend(old.map(|_| Step::Delete));
+    } else {
+        // With the common prefix and suffix gone and neither part empty, at least two steps are
+        // needed, so both halves are smaller than the whole.
+        let (x, y) = middle_snake(eq, old.clone(), new.clone(), frontiers);
+
+        conquer(eq, old.start..x, new.start..y, frontiers, steps);
+        conquer(eq, x..old.end, y..new.end, frontiers, steps);
+    }
+
+    steps.extend((0..suffix).map(|_| Step::Keep));
+}
+
+// Searches forward from the start of `old` and `new` and backward from their ends at once until
+// the two searches meet, and returns where the snake on which they meet starts.
+fn middle_snake(
+    eq: &impl Fn(usize, usize) -> bool,
+    old: Range<usize>,
+    new: Range<usize>,
+    frontiers: &mut Frontiers,
+) -> (usize, usize) {
+    let Frontiers { forward, backward } = frontiers;
+
+    let (n, m) = (old.len(), new.len());
+    let delta = n as isize - m as isize;
+    let odd = delta % 2 != 0;
+
+    let offset = (forward.len() / 2) as isize;
+    let at = |k: isize| (offset + k) as usize;
+
+    forward[at(1)] = 0;
+    backward[at(1)] = 0;
+
+    let mut d = 0;
+
+    loop {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && forward[at(k - 1)] < forward[at(k + 1)]) {
+                forward[at(k + 1)]
+            } else {
+                forward[at(k - 1)] + 1
+            };
+
+            // Wraps around on diagonals that start below the grid, which the bounds checks skip.
+            let mut y = (x as isize - k) as usize;
+            let start = (x, y);
+
+            while x < n && y < m && eq(old.start + x, new.start + y) {
+                x += 1;
+                y += 1;
+            }
+
+            forward[at(k)] = x;
+
+            if odd && (k - delta).abs() < d && x + backward[at(delta - k)] >= n {
+                return (old.start + start.0, new.start + start.1);
+            }
+        }
+
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && backward[at(k - 1)] < backward[at(k + 1)]) {
+                backward[at(k + 1)]
+            } else {
+                backward[at(k - 1)] + 1
+            };
+
+            let mut y = (x as isize - k) as usize;
+
+            while x < n && y < m && eq(old.end - x - 1, new.end - y - 1) {
+                x += 1;
+                y += 1;
+            }
+
+            backward[at(k)] = x;
+
+            if !odd && (k - delta).abs() <= d && x + forward[at(delta - k)] >= n {
+                return (old.end - x, new.end - y);
+            }
+        }
+
+        d += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::diff;
+    use crate::patch::Operation;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn diffing_identical_lists() {
+        let patch = diff(&vec![1, 2, 3], &vec![1, 2, 3]);
+
+        assert!(patch.is_empty());
+    }
+
+    #[test]
+    fn diffing_against_an_empty_list() {
+        let patch = diff(&vec![1, 2, 3], &vec![]);
+
+        assert_eq!(
+            patch.operations(),
+            [
+                Operation::Remove { index: 0 },
+                Operation::Remove { index: 0 },
+                Operation::Remove { index: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn diffing_an_empty_list() {
+        let patch = diff(&vec![], &vec![1, 2, 3]);
+
+        assert_eq!(
+            patch.operations(),
+            [Operation::Insert {
+                index: 0,
+                items: vec![1, 2, 3]
+            }]
+        );
+    }
+
+    #[test]
+    fn diffing_a_replaced_run() {
+        let patch = diff(&vec![1, 2, 3, 4, 5], &vec![1, 6, 7, 8, 5]);
+
+        assert_eq!(
+            patch.operations(),
+            [
+                Operation::Remove { index: 1 },
+                Operation::Remove { index: 1 },
+                Operation::Replace {
+                    index: 1,
+                    items: vec![6, 7, 8]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diffing_different_kinds_of_lists() {
+        let mut items = VecDeque::from([1, 2, 3, 4, 5]);
+
+        let patch = diff(&items, &vec![5, 1, 2, 4]);
+
+        assert_eq!(
+            patch.operations(),
+            [
+                Operation::Insert {
+                    index: 0,
+                    items: vec![5]
+                },
+                Operation::Remove { index: 3 },
+                Operation::Remove { index: 4 },
+            ]
+        );
+
+        patch.apply(&mut items).unwrap();
+
+        assert_eq!(items, VecDeque::from([5, 1, 2, 4]));
+    }
+
+    #[test]
+    fn diffing_all_short_lists() {
+        fn lists() -> Vec<Vec<u8>> {
+            let mut lists = vec![vec![]];
+
+            for len in 1..=4 {
+                for mut n in 0..3usize.pow(len) {
+                    let mut list = Vec::new();
+
+                    for _ in 0..len {
+                        list.push((n % 3) as u8);
+                        n /= 3;
+                    }
+
+                    lists.push(list);
+                }
+            }
+
+            lists
+        }
+
+        fn lcs(a: &[u8], b: &[u8]) -> usize {
+            match (a.split_first(), b.split_first()) {
+                (Some((x, a)), Some((y, b))) if x == y => 1 + lcs(a, b),
+                (Some((_, a_rest)), Some((_, b_rest))) => lcs(a_rest, b).max(lcs(a, b_rest)),
+                _ => 0,
+            }
+        }
+
+        for old in lists() {
+            for new in lists() {
+                let patch = diff(&old, &new);
+
+                let (mut removed, mut inserted) = (0, 0);
+
+                for operation in patch.operations() {
+                    match operation {
+                        Operation::Insert { items, .. } => inserted += items.len(),
+                        Operation::Remove { .. } => removed += 1,
+                        Operation::Replace { items, .. } => {
+                            removed += 1;
+                            inserted += items.len();
+                        }
+                    }
+                }
+
+                let common = lcs(&old, &new);
+                assert_eq!(removed, old.len() - common, "{:?} -> {:?}", old, new);
+                assert_eq!(inserted, new.len() - common, "{:?} -> {:?}", old, new);
+
+                let mut items = old.clone();
+                patch.apply(&mut items).unwrap();
+                assert_eq!(items, new);
+            }
+        }
+    }
+
+    #[test]
+    fn diffing_long_lists() {
+        // A linear congruential generator, so the lists are the same on every run.
+        let mut state = 1u32;
+        let mut next = move || {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (state >> 16) % 4
+        };
+
+        fn lcs(a: &[u32], b: &[u32]) -> usize {
+            let mut lengths = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+            for (i, x) in a.iter().enumerate() {
+                for (j, y) in b.iter().enumerate() {
+                    lengths[i + 1][j + 1] = if x == y {
+                        lengths[i][j] + 1
+                    } else {
+                        lengths[i][j + 1].max(lengths[i + 1][j])
+                    };
+                }
+            }
+
+            lengths[a.len()][b.len()]
+        }
+
+        for _ in 0..100 {
+            let old: Vec<u32> = (0..next() * 20).map(|_| next()).collect();
+            let new: Vec<u32> = (0..next() * 20).map(|_| next()).collect();
+
+            let patch = diff(&old, &new);
+
+            let changed = patch
+                .operations()
+                .iter()
+                .map(|operation| match operation {
+                    Operation::Insert { items, .. } => items.len(),
+                    Operation::Remove { .. } => 1,
+                    Operation::Replace { items, .. } => 1 + items.len(),
+                })
+                .sum::<usize>();
+
+            let common = lcs(&old, &new);
+            assert_eq!(
+                changed,
+                old.len() + new.len() - 2 * common,
+                "{:?} -> {:?}",
+                old,
+                new
+            );
+
+            let mut items = old.clone();
+            patch.apply(&mut items).unwrap();
+            assert_eq!(items, new);
+        }
+    }
+}
@@ -0,0 +1,474 @@
+//! Recording the changes made to a [`List`] so they can be replayed later.
+//!
+//! A [`Patch`] is a sequence of [`Operation`]s. Each operation's index refers to the state of the
+//! list after all of the preceding operations have been applied, so a patch recorded while
+//! editing one list can be applied to a copy of that list to make the same changes.
+//!
+//! ```
+//! use editer::patch::record;
+//!
+//! let mut items = vec![1, 2, 3, 4, 5];
+//! let mut replica = items.clone();
+//!
+//! let patch = record(&mut items, |item| {
+//!     if item == 2 {
+//!         item.insert_after([6, 7]);
+//!     } else if item == 4 {
+//!         item.remove();
+//!     }
+//! });
+//!
+//! patch.apply(&mut replica).unwrap();
+//!
+//! assert_eq!(replica, vec![1, 2, 6, 7, 3, 5]);
+//! assert_eq!(replica, items);
+//! ```
+//!
+//! [`diff`] computes a patch that turns one list into another.
+//!
+//! With the **`serde`** feature enabled, patches can be serialized and deserialized.
+
+use crate::{slot::Slot, List};
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod diff;
+pub use self::diff::diff;
+
+/// Records the changes made by [`edit`](crate::edit)ing `items` with `edit`, returning them as a
+/// [`Patch`].
+///
+/// In-place mutations of an item (e.g. via [`DerefMut`](core::ops::DerefMut)) are recorded as
+/// replacements of that item.
+///
+/// ```
+/// use editer::patch::{record, Operation};
+///
+/// let mut items = vec![1, 2, 3];
+///
+/// let patch = record(&mut items, |mut item| {
+///     if item == 2 {
+///         *item = 4;
+///     }
+/// });
+///
+/// assert_eq!(patch.operations(), [Operation::Replace { index: 1, items: vec![4] }]);
+/// ```
+pub fn record<List>(items: &mut List, edit: impl FnMut(Slot<Recording<List>>)) -> Patch<List::Item>
+where
+    List: self::List + ?Sized,
+    List::Item: Clone,
+{
+    let mut recording = Recording::new(items);
+    crate::edit(&mut recording, edit);
+    recording.into_patch()
+}
+
+/// A sequence of changes to a [`List`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct Patch<Item> {
+    operations: Vec<Operation<Item>>,
+}
+
+impl<Item> Patch<Item> {
+    /// Returns an empty patch.
+    pub fn new() -> Patch<Item> {
+        Patch {
+            operations: Vec::new(),
+        }
+    }
+
+    /// Returns the operations in the patch, in the order they are applied.
+    pub fn operations(&self) -> &[Operation<Item>] {
+        &self.operations
+    }
+
+    /// Returns the number of operations in the patch.
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Returns `true` if the patch contains no operations.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Appends `operation` to the patch.
+    pub fn push(&mut self, operation: Operation<Item>) {
+        self.operations.push(operation)
+    }
+
+    /// Applies each operation in the patch to `list`, in order.
+    ///
+    /// Stops at the first operation whose index is out of bounds and returns an error. Any
+    /// preceding operations will already have been applied.
+    ///
+    /// ```
+    /// use editer::patch::{ApplyError, Operation, Patch};
+    ///
+    /// let mut patch = Patch::new();
+    /// patch.push(Operation::Remove { index: 0 });
+    /// patch.push(Operation::Remove { index: 3 });
+    ///
+    /// let mut items = vec![1, 2, 3];
+    ///
+    /// assert_eq!(patch.apply(&mut items), Err(ApplyError { index: 3, len: 2 }));
+    /// assert_eq!(items, vec![2, 3]);
+    /// ```
+    pub fn apply<List>(self, list: &mut List) -> Result<(), ApplyError>
+    where
+        List: crate::List<Item = Item> + ?Sized,
+    {
+        for operation in self.operations {
+            operation.apply(list)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Item> Default for Patch<Item> {
+    fn default() -> Patch<Item> {
+        Patch::new()
+    }
+}
+
+impl<Item> From<Vec<Operation<Item>>> for Patch<Item> {
+    fn from(operations: Vec<Operation<Item>>) -> Patch<Item> {
+        Patch { operations }
+    }
+}
+
+impl<Item> IntoIterator for Patch<Item> {
+    type Item = Operation<Item>;
+    type IntoIter = alloc::vec::IntoIter<Operation<Item>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.operations.into_iter()
+    }
+}
+
+/// A single change to a [`List`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub enum Operation<Item> {
+    /// Inserts `items` at `index`.
+    Insert { index: usize, items: Vec<Item> },
+
+    /// Removes the item at `index`.
+    Remove { index: usize },
+
+    /// Replaces the item at `index` with zero or more `items`.
+    Replace { index: usize, items: Vec<Item> },
+}
+
+impl<Item> Operation<Item> {
+    /// Applies the operation to `list`, returning an error if its index is out of bounds.
+    pub fn apply<List>(self, list: &mut List) -> Result<(), ApplyError>
+    where
+        List: crate::List<Item = Item> + ?Sized,
+    {
+        let len = list.len();
+
+        match self {
+            Operation::Insert { index, items } if index <= len => {
+                list.insert(index, items.into_iter());
+                Ok(())
+            }
+
+            Operation::Remove { index } if index < len => {
+                list.remove(index);
+                Ok(())
+            }
+
+            Operation::Replace { index, items } if index < len => {
+                list.replace(index, items.into_iter());
+                Ok(())
+            }
+
+            Operation::Insert { index, .. }
+            | Operation::Remove { index }
+            | Operation::Replace { index, .. } => Err(ApplyError { index, len }),
+        }
+    }
+}
+
+/// The error returned when applying a [`Patch`] fails because an operation's index is out of
+/// bounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct ApplyError {
+    /// The out-of-bounds index.
+    pub index: usize,
+
+    /// The length of the list at the time the operation was applied.
+    pub len: usize,
+}
+
+impl Display for ApplyError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "index {} is out of bounds for a list of length {}",
+            self.index, self.len
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ApplyError {}
+
+/// A [`List`] that records every change made to an underlying list in a [`Patch`].
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct Recording<'list, List>
+where
+    List: crate::List + ?Sized,
+{
+    list: &'list mut List,
+    patch: Patch<List::Item>,
+    dirty: Option<usize>,
+}
+
+impl<'list, List> Recording<'list, List>
+where
+    List: crate::List + ?Sized,
+    List::Item: Clone,
+{
+    /// Starts recording the changes made to `list`.
+    pub fn new(list: &'list mut List) -> Recording<'list, List> {
+        Recording {
+            list,
+            patch: Patch::new(),
+            dirty: None,
+        }
+    }
+
+    /// Stops recording and returns the recorded changes.
+    pub fn into_patch(mut self) -> Patch<List::Item> {
+        self.flush();
+        self.patch
+    }
+
+    // Items borrowed mutably may have changed in place. We can't tell whether they did until the
+    // borrow ends, so we record them as replacements before the next structural change.
+    fn flush(&mut self) {
+        if let Some(index) = self.dirty.take() {
+            self.patch.push(Operation::Replace {
+                index,
+                items: Vec::from([self.list.index(index).clone()]),
+            });
+        }
+    }
+
+    fn discard(&mut self, index: usize) {
+        if self.dirty == Some(index) {
+            self.dirty = None;
+        } else {
+            self.flush();
+        }
+    }
+}
+
+impl<'list, List> crate::List for Recording<'list, List>
+where
+    List: crate::List + ?Sized,
+    List::Item: Clone,
+{
+    type Item = List::Item;
+
+    fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    fn index(&self, index: usize) -> &Self::Item {
+        self.list.index(index)
+    }
+
+    fn index_mut(&mut self, index: usize) -> &mut Self::Item {
+        if self.dirty != Some(index) {
+            self.flush();
+            self.dirty = Some(index);
+        }
+
+        self.list.index_mut(index)
+    }
+
+    fn insert(&mut self, index: usize, items: impl ExactSizeIterator<Item = Self::Item>) {
+        self.flush();
+
+        let items: Vec<_> = items.collect();
+        self.list.insert(index, items.iter().cloned());
+        self.patch.push(Operation::Insert { index, items });
+    }
+
+    fn remove(&mut self, index: usize) -> Self::Item {
+        self.discard(index);
+
+        let item = self.list.remove(index);
+        self.patch.push(Operation::Remove { index });
+        item
+    }
+
+    fn replace(&mut self, index: usize, items: impl ExactSizeIterator<Item = Self::Item>) {
+        self.discard(index);
+
+        let items: Vec<_> = items.collect();
+        self.list.replace(index, items.iter().cloned());
+        self.patch.push(Operation::Replace { index, items });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record, ApplyError, Operation, Patch};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn recording_and_applying_an_edit() {
+        let mut items = vec![1, 2, 3, 4, 5];
+        let mut replica = VecDeque::from([1, 2, 3, 4, 5]);
+
+        let patch = record(&mut items, |mut item| {
+            if item == 1 {
+                item.insert_before([6]);
+            } else if item == 2 {
+                *item = 7;
+            } else if item == 3 {
+                item.replace([8, 9]);
+            } else if item == 4 {
+                item.remove();
+            } else if item == 5 {
+                item.insert_after([10, 11]);
+            }
+        });
+
+        assert_eq!(
+            patch.operations(),
+            [
+                Operation::Insert {
+                    index: 0,
+                    items: vec![6]
+                },
+                Operation::Replace {
+                    index: 2,
+                    items: vec![7]
+                },
+                Operation::Replace {
+                    index: 3,
+                    items: vec![8, 9]
+                },
+                Operation::Remove { index: 5 },
+                Operation::Insert {
+                    index: 6,
+                    items: vec![10, 11]
+                },
+            ]
+        );
+
+        patch.apply(&mut replica).unwrap();
+
+        assert_eq!(items, vec![6, 1, 7, 8, 9, 5, 10, 11]);
+        assert_eq!(replica, VecDeque::from([6, 1, 7, 8, 9, 5, 10, 11]));
+    }
+
+    #[test]
+    fn recording_a_mutation_followed_by_a_replacement() {
+        let mut items = vec![1, 2, 3];
+
+        let patch = record(&mut items, |mut item| {
+            if item == 2 {
+                *item = 4;
+                item.replace([5, 6]);
+            }
+        });
+
+        assert_eq!(
+            patch.operations(),
+            [Operation::Replace {
+                index: 1,
+                items: vec![5, 6]
+            }]
+        );
+    }
+
+    #[test]
+    fn recording_no_changes() {
+        let mut items = vec![1, 2, 3];
+
+        let patch = record(&mut items, |_| {});
+
+        assert!(patch.is_empty());
+    }
+
+    #[test]
+    fn applying_an_out_of_bounds_insertion() {
+        let patch = Patch::from(vec![Operation::Insert {
+            index: 4,
+            items: vec![4],
+        }]);
+        let mut items = vec![1, 2, 3];
+
+        assert_eq!(
+            patch.apply(&mut items),
+            Err(ApplyError { index: 4, len: 3 })
+        );
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn applying_an_out_of_bounds_replacement() {
+        let patch = Patch::from(vec![Operation::Replace {
+            index: 3,
+            items: vec![4],
+        }]);
+        let mut items = vec![1, 2, 3];
+
+        assert_eq!(
+            patch.apply(&mut items),
+            Err(ApplyError { index: 3, len: 3 })
+        );
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    fn round_tripping_a_patch_through_json() {
+        let mut items = vec![1, 2, 3];
+
+        let patch = record(&mut items, |item| {
+            if item == 2 {
+                item.replace([4, 5]);
+            } else if item == 3 {
+                item.remove();
+            }
+        });
+
+        let json = serde_json::to_string(&patch).unwrap();
+        let decoded: Patch<i32> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(decoded, patch);
+
+        let mut replica = vec![1, 2, 3];
+        decoded.apply(&mut replica).unwrap();
+
+        assert_eq!(replica, items);
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    fn round_tripping_an_apply_error_through_json() {
+        let error = ApplyError { index: 4, len: 3 };
+
+        let json = serde_json::to_string(&error).unwrap();
+
+        assert_eq!(json, r#"{"index":4,"len":3}"#);
+        assert_eq!(serde_json::from_str::<ApplyError>(&json).unwrap(), error);
+    }
+}
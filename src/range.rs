@@ -0,0 +1,182 @@
+//! Viewing a run of consecutive items in a list.
+//!
+//! [`RangeSlot`] is shared by [`ChunkSlot`](crate::chunks::ChunkSlot),
+//! [`WindowSlot`](crate::windows::WindowSlot) and [`GroupSlot`](crate::groups::GroupSlot), which
+//! dereference to it for accessing their items.
+
+use crate::List;
+use core::{
+    fmt::Debug,
+    iter,
+    ops::{Index, IndexMut},
+};
+
+/// A run of consecutive items in a list, which can be accessed like a slice.
+pub struct RangeSlot<'list, List>
+where
+    List: self::List + ?Sized,
+{
+    list: &'list mut List,
+    index: usize,
+    len: usize,
+}
+
+impl<'list, List> RangeSlot<'list, List>
+where
+    List: self::List + ?Sized,
+{
+    pub(crate) fn new(list: &'list mut List, index: usize, len: usize) -> RangeSlot<'list, List> {
+        RangeSlot { list, index, len }
+    }
+
+    /// Returns the number of items in the range.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the range contains no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the index in the list of the first item in the range.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns a shared reference to the item at `index` within the range, or `None` if `index`
+    /// is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&List::Item> {
+        if index < self.len {
+            Some(self.list.index(self.index + index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the item at `index` within the range, or `None` if `index`
+    /// is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut List::Item> {
+        if index < self.len {
+            Some(self.list.index_mut(self.index + index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the items in the range.
+    pub fn iter(&self) -> impl Iterator<Item = &List::Item> + '_ {
+        (self.index..self.index + self.len).map(move |index| self.list.index(index))
+    }
+
+    /// Inserts `items` `offset` items into the range, returning the number of items inserted.
+    pub(crate) fn insert(
+        self,
+        offset: usize,
+        items: impl ExactSizeIterator<Item = List::Item>,
+    ) -> usize {
+        let count = items.len();
+        self.list.insert(self.index + offset, items);
+        count
+    }
+
+    /// Replaces the items in the range with `items`, returning the number of items that replaced
+    /// them.
+    pub(crate) fn replace(self, items: impl ExactSizeIterator<Item = List::Item>) -> usize {
+        let count = items.len();
+        self.list
+            .replace_range(self.index..self.index + self.len, items);
+        count
+    }
+
+    /// Removes the items in the range.
+    pub(crate) fn remove(self) {
+        self.replace(iter::empty());
+    }
+
+    /// Merges the items in the range into the first one, by calling `merge` with the first item
+    /// and each subsequent item in order.
+    pub(crate) fn merge(self, mut merge: impl FnMut(&mut List::Item, List::Item)) {
+        for _ in 1..self.len {
+            let item = self.list.remove(self.index + 1);
+            merge(self.list.index_mut(self.index), item);
+        }
+    }
+}
+
+impl<'list, List> Index<usize> for RangeSlot<'list, List>
+where
+    List: self::List + ?Sized,
+{
+    type Output = List::Item;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("range index out of bounds")
+    }
+}
+
+impl<'list, List> IndexMut<usize> for RangeSlot<'list, List>
+where
+    List: self::List + ?Sized,
+{
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("range index out of bounds")
+    }
+}
+
+impl<'list, List, Item> PartialEq<[Item]> for RangeSlot<'list, List>
+where
+    List: self::List + ?Sized,
+    List::Item: PartialEq<Item>,
+{
+    fn eq(&self, other: &[Item]) -> bool {
+        self.len == other.len() && self.iter().zip(other).all(|(item, other)| item == other)
+    }
+}
+
+impl<'list, List, Item, const N: usize> PartialEq<[Item; N]> for RangeSlot<'list, List>
+where
+    List: self::List + ?Sized,
+    List::Item: PartialEq<Item>,
+{
+    fn eq(&self, other: &[Item; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<'list, List> Debug for RangeSlot<'list, List>
+where
+    List: self::List + ?Sized,
+    List::Item: Debug,
+{
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RangeSlot;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn accessing_items_in_a_range() {
+        let mut items = VecDeque::from([1, 2, 3, 4]);
+        let mut range = RangeSlot::new(&mut items, 1, 2);
+
+        range[1] *= 10;
+
+        assert_eq!(range.get(2), None);
+        assert_eq!(range, [2, 30]);
+        assert_eq!(format!("{:?}", range), "[2, 30]");
+    }
+
+    #[test]
+    #[should_panic(expected = "range index out of bounds")]
+    fn indexing_past_the_end_of_a_range() {
+        let mut items = vec![1, 2, 3];
+        let range = RangeSlot::new(&mut items, 1, 1);
+
+        let _ = range[1];
+    }
+}
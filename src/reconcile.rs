@@ -0,0 +1,321 @@
+//! Updating a [`List`] in place to match a target list by stable keys.
+
+use crate::{observer::EditObserver, List};
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    vec::Vec,
+};
+use core::iter;
+
+/// Updates `list` in place so that it matches `target`, identifying items by the keys returned
+/// by `key`.
+///
+/// Items in `list` whose keys appear in `target` are kept, moved into position if necessary, and
+/// passed to `update` along with the corresponding item from `target`. Items whose keys don't
+/// appear in `target` are removed, and items from `target` whose keys don't appear in `list` are
+/// inserted.
+///
+/// ```
+/// use editer::reconcile::reconcile;
+///
+/// let mut items = vec![("a", 1), ("b", 2), ("c", 3)];
+///
+/// reconcile(
+///     &mut items,
+///     [("c", 4), ("d", 5), ("a", 6)],
+///     |item| item.0,
+///     |item, target| item.1 = target.1,
+/// );
+///
+/// assert_eq!(items, vec![("c", 4), ("d", 5), ("a", 6)]);
+/// ```
+pub fn reconcile<List, Key>(
+    list: &mut List,
+    target: impl IntoIterator<Item = List::Item>,
+    key: impl FnMut(&List::Item) -> Key,
+    update: impl FnMut(&mut List::Item, List::Item),
+) where
+    List: self::List + ?Sized,
+    Key: Ord,
+{
+    reconcile_observed(list, target, key, update, &mut ())
+}
+
+/// Like [`reconcile`], but reports each change made to `list` to `observer`.
+///
+/// Moves are reported via [`EditObserver::on_move`], and insertions of items from `target` are
+/// reported one at a time.
+///
+/// ```
+/// use editer::{observer::EditObserver, reconcile::reconcile_observed};
+///
+/// #[derive(Default)]
+/// struct Log(Vec<String>);
+///
+/// impl EditObserver for Log {
+///     fn on_insert(&mut self, index: usize, count: usize) {
+///         self.0.push(format!("insert {} at {}", count, index));
+///     }
+///
+///     fn on_remove(&mut self, index: usize) {
+///         self.0.push(format!("remove {}", index));
+///     }
+///
+///     fn on_move(&mut self, from: usize, to: usize) {
+///         self.0.push(format!("move {} to {}", from, to));
+///     }
+/// }
+///
+/// let mut items = vec!["a", "b", "c"];
+/// let mut log = Log::default();
+///
+/// reconcile_observed(&mut items, ["c", "d", "a"], |item| *item, |_, _| {}, &mut log);
+///
+/// assert_eq!(items, vec!["c", "d", "a"]);
+/// assert_eq!(log.0, ["remove 1", "move 1 to 0", "insert 1 at 1"]);
+/// ```
+pub fn reconcile_observed<List, Key>(
+    list: &mut List,
+    target: impl IntoIterator<Item = List::Item>,
+    mut key: impl FnMut(&List::Item) -> Key,
+    mut update: impl FnMut(&mut List::Item, List::Item),
+    observer: &mut impl EditObserver,
+) where
+    List: self::List + ?Sized,
+    Key: Ord,
+{
+    let target: Vec<_> = target.into_iter().collect();
+
+    // Line up the occurrences of each key in the target, so that the nth item with a key in the
+    // list is matched with the nth item with that key in the target.
+    let mut wanted = BTreeMap::new();
+
+    for (index, item) in target.iter().enumerate() {
+        wanted
+            .entry(key(item))
+            .or_insert_with(VecDeque::new)
+            .push_back(index);
+    }
+
+    // The item kept in the list for each item in the target, by its position among the kept
+    // items, or `None` where there's no counterpart.
+    let mut sources = alloc::vec![None; target.len()];
+    let mut index = 0;
+
+    while index < list.len() {
+        match wanted
+            .get_mut(&key(list.index(index)))
+            .and_then(VecDeque::pop_front)
+        {
+            Some(destination) => {
+                sources[destination] = Some(index);
+                index += 1;
+            }
+
+            None => {
+                list.remove(index);
+                observer.on_remove(index);
+            }
+        }
+    }
+
+    // Every item left in the list now has a counterpart in the target. Walk the target, moving
+    // each counterpart into position or inserting a new item where there's none. The kept items
+    // that are yet to be moved stay in their original order after the ones already in position.
+    let mut unplaced = Unplaced::new(list.len());
+
+    for ((index, item), source) in target.into_iter().enumerate().zip(sources) {
+        match source {
+            Some(source) => {
+                let current = index + unplaced.before(source);
+                unplaced.place(source);
+
+                if current != index {
+                    list.move_item(current, index);
+                    observer.on_move(current, index);
+                }
+
+                update(list.index_mut(index), item);
+            }
+
+            None => {
+                list.insert(index, iter::once(item));
+                observer.on_insert(index, 1);
+            }
+        }
+    }
+}
+
+/// Counts the kept items that have yet to be moved into position that came before a given kept
+/// item originally, using a Fenwick tree.
+struct Unplaced(Vec<usize>);
+
+impl Unplaced {
+    fn new(len: usize) -> Unplaced {
+        let mut tree = alloc::vec![0; len + 1];
+
+        for index in 1..=len {
+            tree[index] += 1;
+
+            let parent = index + (index & index.wrapping_neg());
+
+            if parent <= len {
+                tree[parent] += tree[index];
+            }
+        }
+
+        Unplaced(tree)
+    }
+
+    fn before(&self, source: usize) -> usize {
+        let mut count = 0;
+        let mut index = source;
+
+        while index > 0 {
+            count += self.0[index];
+            index &= index - 1;
+        }
+
+        count
+    }
+
+    fn place(&mut self, source: usize) {
+        let mut index = source + 1;
+
+        while index < self.0.len() {
+            self.0[index] -= 1;
+            index += index & index.wrapping_neg();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reconcile, reconcile_observed};
+    use crate::observer::EditObserver;
+    use std::collections::VecDeque;
+
+    #[derive(Default)]
+    struct Log(Vec<(&'static str, usize, usize)>);
+
+    impl EditObserver for Log {
+        fn on_insert(&mut self, index: usize, count: usize) {
+            self.0.push(("insert", index, count));
+        }
+
+        fn on_remove(&mut self, index: usize) {
+            self.0.push(("remove", index, index));
+        }
+
+        fn on_move(&mut self, from: usize, to: usize) {
+            self.0.push(("move", from, to));
+        }
+    }
+
+    #[test]
+    fn reconciling_identical_lists() {
+        let mut items = vec![("a", 1), ("b", 2), ("c", 3)];
+        let mut log = Log::default();
+
+        reconcile_observed(
+            &mut items,
+            [("a", 4), ("b", 5), ("c", 6)],
+            |item| item.0,
+            |item, target| item.1 = target.1,
+            &mut log,
+        );
+
+        assert_eq!(items, vec![("a", 4), ("b", 5), ("c", 6)]);
+        assert!(log.0.is_empty());
+    }
+
+    #[test]
+    fn reconciling_with_an_empty_target() {
+        let mut items = vec![1, 2, 3];
+        let mut log = Log::default();
+
+        reconcile_observed(&mut items, [], |item| *item, |_, _| {}, &mut log);
+
+        assert!(items.is_empty());
+        assert_eq!(
+            log.0,
+            [("remove", 0, 0), ("remove", 0, 0), ("remove", 0, 0)]
+        );
+    }
+
+    #[test]
+    fn reconciling_an_empty_list() {
+        let mut items = VecDeque::new();
+        let mut log = Log::default();
+
+        reconcile_observed(&mut items, [1, 2, 3], |item| *item, |_, _| {}, &mut log);
+
+        assert_eq!(items, VecDeque::from([1, 2, 3]));
+        assert_eq!(
+            log.0,
+            [("insert", 0, 1), ("insert", 1, 1), ("insert", 2, 1)]
+        );
+    }
+
+    #[test]
+    fn reconciling_a_reversed_list() {
+        let mut items = vec![1, 2, 3, 4];
+        let mut log = Log::default();
+
+        reconcile_observed(&mut items, [4, 3, 2, 1], |item| *item, |_, _| {}, &mut log);
+
+        assert_eq!(items, vec![4, 3, 2, 1]);
+        assert_eq!(log.0, [("move", 3, 0), ("move", 3, 1), ("move", 3, 2)]);
+    }
+
+    #[test]
+    fn reconciling_updates_kept_items_in_place() {
+        let mut items = vec![(1, String::from("one")), (2, String::from("two"))];
+
+        reconcile(
+            &mut items,
+            [(2, String::from("deux")), (3, String::from("trois"))],
+            |item| item.0,
+            |item, target| item.1 = target.1,
+        );
+
+        assert_eq!(
+            items,
+            vec![(2, String::from("deux")), (3, String::from("trois"))]
+        );
+    }
+
+    #[test]
+    fn reconciling_duplicate_keys() {
+        let mut items = vec![("a", 1), ("a", 2), ("a", 3), ("b", 4)];
+
+        reconcile(
+            &mut items,
+            [("b", 5), ("a", 6), ("a", 7)],
+            |item| item.0,
+            |item, target| item.1 += target.1,
+        );
+
+        assert_eq!(items, vec![("b", 9), ("a", 7), ("a", 9)]);
+    }
+
+    #[test]
+    fn reconciling_calls_key_once_per_item() {
+        let mut items: Vec<usize> = (0..100).collect();
+        let target: Vec<usize> = (0..100).map(|index| index * 37 % 101).collect();
+        let mut calls = 0;
+
+        reconcile(
+            &mut items,
+            target.clone(),
+            |item| {
+                calls += 1;
+                *item
+            },
+            |_, _| {},
+        );
+
+        assert_eq!(items, target);
+        assert_eq!(calls, 200);
+    }
+}
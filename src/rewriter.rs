@@ -0,0 +1,307 @@
+//! Rewriting a list with a set of local rules until none of them apply.
+
+use crate::{windows::WindowSlot, List};
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt::{self, Display};
+
+/// The number of passes a [`Rewriter`] makes before giving up, unless configured otherwise.
+pub const DEFAULT_PASS_LIMIT: usize = 100;
+
+/// An ordered set of rewrite rules, which are applied to a list in passes until a pass makes no
+/// changes.
+///
+/// Each rule has a name, a window size, a predicate over windows of that size and a builder for
+/// the items that replace a matching window. In each pass, the rewriter tries the rules in order
+/// at each position in the list, and applies the first one that matches. It then moves on past
+/// the replacement items, which are only considered again in the next pass.
+///
+/// ```
+/// use editer::rewriter::Rewriter;
+///
+/// let mut tokens = vec!["x", "*", "1", "+", "0", "*", "y"];
+///
+/// let report = Rewriter::new()
+///     .rule("multiply by one", 2, |w| *w == ["*", "1"], |_| vec![])
+///     .rule("add zero", 2, |w| *w == ["+", "0"], |_| vec![])
+///     .run(&mut tokens)
+///     .unwrap();
+///
+/// assert_eq!(tokens, vec!["x", "*", "y"]);
+/// assert_eq!(report.count("multiply by one"), 1);
+/// assert_eq!(report.count("add zero"), 1);
+/// assert_eq!(report.passes, 2);
+/// ```
+pub struct Rewriter<'rules, List>
+where
+    List: self::List + ?Sized,
+{
+    rules: Vec<Rule<'rules, List>>,
+    pass_limit: usize,
+}
+
+type Predicate<'rules, List> = Box<dyn FnMut(&WindowSlot<List>) -> bool + 'rules>;
+
+type Builder<'rules, List> =
+    Box<dyn FnMut(&WindowSlot<List>) -> Vec<<List as self::List>::Item> + 'rules>;
+
+struct Rule<'rules, List>
+where
+    List: self::List + ?Sized,
+{
+    name: &'static str,
+    size: usize,
+    matches: Predicate<'rules, List>,
+    build: Builder<'rules, List>,
+}
+
+impl<'rules, List> Rewriter<'rules, List>
+where
+    List: self::List + ?Sized,
+{
+    /// Returns a rewriter with no rules and the [default pass limit](DEFAULT_PASS_LIMIT).
+    pub fn new() -> Rewriter<'rules, List> {
+        Rewriter {
+            rules: Vec::new(),
+            pass_limit: DEFAULT_PASS_LIMIT,
+        }
+    }
+
+    /// Adds a rule named `name`, which replaces windows of `size` items for which `matches`
+    /// returns `true` with the items returned by `build`.
+    ///
+    /// Rules added earlier take precedence over rules added later.
+    ///
+    /// Panics if `size` is zero.
+    pub fn rule(
+        mut self,
+        name: &'static str,
+        size: usize,
+        matches: impl FnMut(&WindowSlot<List>) -> bool + 'rules,
+        build: impl FnMut(&WindowSlot<List>) -> Vec<List::Item> + 'rules,
+    ) -> Rewriter<'rules, List> {
+        assert!(size != 0, "window size must be non-zero");
+
+        self.rules.push(Rule {
+            name,
+            size,
+            matches: Box::new(matches),
+            build: Box::new(build),
+        });
+
+        self
+    }
+
+    /// Sets the number of passes the rewriter makes before giving up.
+    ///
+    /// Panics if `pass_limit` is zero, since even a list that needs no rewriting takes a pass to
+    /// find that out.
+    pub fn pass_limit(mut self, pass_limit: usize) -> Rewriter<'rules, List> {
+        assert!(pass_limit != 0, "pass limit must be non-zero");
+        self.pass_limit = pass_limit;
+        self
+    }
+
+    /// Applies the rules to `list` in passes until a pass makes no changes, returning a report of
+    /// the rules applied.
+    ///
+    /// If that doesn't happen within the pass limit, stops with an error. The list is left as it
+    /// was after the last pass.
+    ///
+    /// ```
+    /// use editer::rewriter::Rewriter;
+    ///
+    /// let mut items = vec![1, 2];
+    ///
+    /// let error = Rewriter::new()
+    ///     .rule("swap", 2, |_| true, |window| vec![window[1], window[0]])
+    ///     .pass_limit(3)
+    ///     .run(&mut items)
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.report.passes, 3);
+    /// assert_eq!(error.report.count("swap"), 3);
+    /// ```
+    pub fn run(&mut self, list: &mut List) -> Result<RewriteReport, PassLimitError> {
+        let mut report = RewriteReport {
+            passes: 0,
+            fired: self.rules.iter().map(|rule| (rule.name, 0)).collect(),
+        };
+
+        loop {
+            if report.passes == self.pass_limit {
+                return Err(PassLimitError { report });
+            }
+
+            report.passes += 1;
+
+            if !self.pass(list, &mut report) {
+                return Ok(report);
+            }
+        }
+    }
+
+    /// Makes a single pass over `list`, returning `true` if any rule was applied.
+    fn pass(&mut self, list: &mut List, report: &mut RewriteReport) -> bool {
+        let mut index = 0;
+        let mut changed = false;
+
+        'positions: while index < list.len() {
+            for (rule, fired) in self.rules.iter_mut().zip(&mut report.fired) {
+                if index + rule.size > list.len() {
+                    continue;
+                }
+
+                let mut rewritten = false;
+                let window = WindowSlot::new(list, index, rule.size, true, &mut rewritten);
+
+                if (rule.matches)(&window) {
+                    let items = (rule.build)(&window);
+                    let count = items.len();
+                    window.replace(items);
+
+                    fired.1 += 1;
+                    changed = true;
+                    index += count;
+                    continue 'positions;
+                }
+            }
+
+            index += 1;
+        }
+
+        changed
+    }
+}
+
+impl<'rules, List> Default for Rewriter<'rules, List>
+where
+    List: self::List + ?Sized,
+{
+    fn default() -> Rewriter<'rules, List> {
+        Rewriter::new()
+    }
+}
+
+/// Describes the rules applied by [`Rewriter::run`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RewriteReport {
+    /// The number of passes made, including the final pass, which made no changes unless the
+    /// pass limit was reached.
+    pub passes: usize,
+
+    /// The name of each rule, in order, along with the number of times it was applied.
+    pub fired: Vec<(&'static str, usize)>,
+}
+
+impl RewriteReport {
+    /// Returns the number of times the rules named `name` were applied.
+    pub fn count(&self, name: &str) -> usize {
+        self.fired
+            .iter()
+            .filter(|(rule, _)| *rule == name)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Returns the total number of times any rule was applied.
+    pub fn total(&self) -> usize {
+        self.fired.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// The error returned by [`Rewriter::run`] when the rules still apply after the pass limit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PassLimitError {
+    /// The rules applied before giving up.
+    pub report: RewriteReport,
+}
+
+impl Display for PassLimitError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "rules still applied after {} passes",
+            self.report.passes
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PassLimitError {}
+
+#[cfg(test)]
+mod tests {
+    use super::Rewriter;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn running_to_a_fixpoint() {
+        let mut items = VecDeque::from(['(', '(', '[', ']', ')', ')', '(']);
+
+        let report = Rewriter::new()
+            .rule("parentheses", 2, |w| *w == ['(', ')'], |_| vec![])
+            .rule("brackets", 2, |w| *w == ['[', ']'], |_| vec![])
+            .run(&mut items)
+            .unwrap();
+
+        assert_eq!(items, VecDeque::from(['(']));
+        assert_eq!(report.fired, [("parentheses", 2), ("brackets", 1)]);
+        assert_eq!(report.total(), 3);
+        assert_eq!(report.passes, 4);
+    }
+
+    #[test]
+    fn preferring_earlier_rules() {
+        let mut items = vec![1, 2, 3];
+
+        let report = Rewriter::new()
+            .rule("pair", 2, |w| *w == [1, 2], |_| vec![12])
+            .rule("triple", 3, |w| *w == [1, 2, 3], |_| vec![123])
+            .run(&mut items)
+            .unwrap();
+
+        assert_eq!(items, vec![12, 3]);
+        assert_eq!(report.count("pair"), 1);
+        assert_eq!(report.count("triple"), 0);
+    }
+
+    #[test]
+    fn not_revisiting_replacements_in_the_same_pass() {
+        let mut items = vec![1, 1];
+        let mut windows = Vec::new();
+
+        let report = Rewriter::new()
+            .rule(
+                "double",
+                1,
+                |w| {
+                    windows.push(w[0]);
+                    w[0] < 4
+                },
+                |w| vec![w[0] * 2],
+            )
+            .run(&mut items)
+            .unwrap();
+
+        assert_eq!(items, vec![4, 4]);
+        assert_eq!(report.passes, 3);
+        assert_eq!(windows, [1, 1, 2, 2, 4, 4]);
+    }
+
+    #[test]
+    fn running_without_rules() {
+        let mut items = vec![1, 2, 3];
+
+        let report = Rewriter::new().run(&mut items).unwrap();
+
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(report.passes, 1);
+        assert_eq!(report.total(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "pass limit must be non-zero")]
+    fn limiting_to_zero_passes() {
+        Rewriter::<Vec<i32>>::new().pass_limit(0);
+    }
+}
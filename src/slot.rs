@@ -1,35 +1,55 @@
-use crate::Stride;
+use crate::{observer::EditObserver, Change, Stride};
 use core::{
     fmt::{Debug, Display},
+    iter,
     ops::{Deref, DerefMut},
 };
 
 /// Represents the current position in a mutating iteration of a list.
-pub struct Slot<'list, 'stride, List>
+///
+/// Changes made through the `Slot` are reported to its [`EditObserver`], if any. See
+/// [`edit_observed`](crate::edit_observed).
+pub struct Slot<'list, 'stride, List, Observer = ()>
 where
     List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
 {
     list: &'list mut List,
     index: usize,
     stride: &'stride mut Stride,
+    observer: &'stride mut Observer,
 }
 
-impl<'list, 'stride, List> Slot<'list, 'stride, List>
+impl<'list, 'stride, List, Observer> Slot<'list, 'stride, List, Observer>
 where
     List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
 {
     pub(crate) fn new(
         list: &'list mut List,
         index: usize,
         stride: &'stride mut Stride,
-    ) -> Slot<'list, 'stride, List> {
+        observer: &'stride mut Observer,
+    ) -> Slot<'list, 'stride, List, Observer> {
         Slot {
             list,
             index,
             stride,
+            observer,
         }
     }
 
+    pub(crate) fn into_parts(
+        self,
+    ) -> (
+        &'list mut List,
+        usize,
+        &'stride mut Stride,
+        &'stride mut Observer,
+    ) {
+        (self.list, self.index, self.stride, self.observer)
+    }
+
     /// Returns a shared reference to the item at the current position.
     pub fn get(&self) -> &List::Item {
         self.list.index(self.index)
@@ -40,6 +60,77 @@ where
         self.list.index_mut(self.index)
     }
 
+    /// Edits the current item, which is itself a list, by calling `edit` with a `Slot` for each
+    /// of its items, as [`edit`](crate::edit) does.
+    ///
+    /// ```
+    /// # use editer::edit;
+    /// #
+    /// let mut rows = vec![vec![1, 2], vec![], vec![3, 4]];
+    ///
+    /// edit(&mut rows, |mut row| {
+    ///     if row.is_empty() {
+    ///         row.remove();
+    ///     } else {
+    ///         row.edit_inner(|cell| {
+    ///             if cell == 3 {
+    ///                 cell.remove();
+    ///             }
+    ///         });
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(rows, vec![vec![1, 2], vec![4]]);
+    /// ```
+    pub fn edit_inner(&mut self, edit: impl FnMut(Slot<List::Item>))
+    where
+        List::Item: crate::List,
+    {
+        crate::edit(self.get_mut(), edit)
+    }
+
+    /// Like [`edit_inner`](Slot::edit_inner), but edits the list returned by `project` for the
+    /// current item.
+    ///
+    /// ```
+    /// # use editer::edit;
+    /// #
+    /// struct Section {
+    ///     title: &'static str,
+    ///     lines: Vec<&'static str>,
+    /// }
+    ///
+    /// let mut sections = vec![
+    ///     Section { title: "draft", lines: vec!["a", "TODO", "b"] },
+    ///     Section { title: "final", lines: vec!["TODO", "c"] },
+    /// ];
+    ///
+    /// edit(&mut sections, |mut section| {
+    ///     if section.title == "final" {
+    ///         section.edit_inner_by(
+    ///             |section| &mut section.lines,
+    ///             |line| {
+    ///                 if line == "TODO" {
+    ///                     line.remove();
+    ///                 }
+    ///             },
+    ///         );
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(sections[0].lines, vec!["a", "TODO", "b"]);
+    /// assert_eq!(sections[1].lines, vec!["c"]);
+    /// ```
+    pub fn edit_inner_by<Inner>(
+        &mut self,
+        project: impl FnOnce(&mut List::Item) -> &mut Inner,
+        edit: impl FnMut(Slot<Inner>),
+    ) where
+        Inner: crate::List + ?Sized,
+    {
+        crate::edit(project(self.get_mut()), edit)
+    }
+
     /// Inserts zero or more `items` before the current item.
     ///
     /// ```
@@ -61,8 +152,13 @@ where
         Items::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
     {
         let items = items.into_iter();
-        self.stride.set(items.len() + 1);
+        let count = items.len();
+        self.stride.set(count + 1);
+        self.stride.summary().inserted += count;
         self.list.insert(self.index, items);
+        self.stride
+            .record(self.index, Change::InsertedBefore(count));
+        self.observer.on_insert(self.index, count);
     }
 
     /// Inserts zero or more `items` after the current item.
@@ -86,8 +182,82 @@ where
         Items::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
     {
         let items = items.into_iter();
-        self.stride.set(items.len() + 1);
+        let count = items.len();
+        self.stride.set(count + 1);
+        self.stride.summary().inserted += count;
         self.list.insert(self.index + 1, items);
+        self.stride.record(self.index, Change::InsertedAfter(count));
+        self.observer.on_insert(self.index + 1, count);
+    }
+
+    /// Appends zero or more `items` to the end of the list, without affecting the current item.
+    ///
+    /// The items are visited later in the same edit, which makes `push_back` suitable for
+    /// worklist algorithms. They're inserted before any items moved with
+    /// [`move_to_end`](Slot::move_to_end), which stay at the end of the list.
+    ///
+    /// ```
+    /// # use editer::edit;
+    /// #
+    /// let mut items = vec![12, 5];
+    ///
+    /// edit(&mut items, |mut item| {
+    ///     if *item > 9 {
+    ///         item.push_back([*item / 10]);
+    ///         *item %= 10;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(items, vec![2, 5, 1]);
+    /// ```
+    pub fn push_back<Items>(&mut self, items: Items)
+    where
+        Items: IntoIterator,
+        Items::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
+    {
+        let items = items.into_iter();
+        let count = items.len();
+        let index = self.list.len() - self.stride.tail();
+        self.stride.pushed_back(count);
+        self.stride.summary().inserted += count;
+        self.list.insert(index, items);
+        self.stride.record(self.index, Change::PushedBack(count));
+        self.observer.on_insert(index, count);
+    }
+
+    /// Prepends zero or more `items` to the start of the list, without affecting the current
+    /// item.
+    ///
+    /// The items aren't visited in the same edit. The current item, and every item after it,
+    /// shifts towards the end of the list by the number of items pushed.
+    ///
+    /// ```
+    /// # use editer::edit;
+    /// #
+    /// let mut items = vec![1, 2, 3];
+    ///
+    /// edit(&mut items, |mut item| {
+    ///     if item == 2 {
+    ///         item.push_front([4, 5]);
+    ///         *item = 6;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(items, vec![4, 5, 1, 6, 3]);
+    /// ```
+    pub fn push_front<Items>(&mut self, items: Items)
+    where
+        Items: IntoIterator,
+        Items::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
+    {
+        let items = items.into_iter();
+        let count = items.len();
+        self.stride.pushed_front(count);
+        self.stride.summary().inserted += count;
+        self.list.insert(0, items);
+        self.stride.record(self.index, Change::PushedFront(count));
+        self.observer.on_insert(0, count);
+        self.index += count;
     }
 
     /// Replaces the current item with zero or more `items`.
@@ -111,8 +281,12 @@ where
         Items::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
     {
         let items = items.into_iter();
-        self.stride.set(items.len());
+        let count = items.len();
+        self.stride.set(count);
+        self.stride.summary().replaced += 1;
         self.list.replace(self.index, items);
+        self.stride.record(self.index, Change::Replaced(count));
+        self.observer.on_replace(self.index, count);
     }
 
     /// Calls `build` with a shared reference to the current item. Replaces the current item with
@@ -184,14 +358,133 @@ where
     /// assert_eq!(items, vec![1, 2, 4, 5]);
     /// ```
     pub fn remove(self) {
-        self.list.remove(self.index);
+        self.take();
+    }
+
+    /// Removes the current item and moves it into `sink`.
+    ///
+    /// ```
+    /// # use editer::edit;
+    /// #
+    /// let mut items = vec![1, 2, 3, 4, 5];
+    /// let mut evens = Vec::new();
+    ///
+    /// edit(&mut items, |item| {
+    ///     if *item % 2 == 0 {
+    ///         item.remove_into(&mut evens);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(items, vec![1, 3, 5]);
+    /// assert_eq!(evens, vec![2, 4]);
+    /// ```
+    pub fn remove_into(self, sink: &mut impl Extend<List::Item>) {
+        sink.extend(iter::once(self.take()));
+    }
+
+    /// Moves the current item to the start of the list. It won't be visited again.
+    ///
+    /// ```
+    /// # use editer::edit;
+    /// #
+    /// let mut items = vec![1, 2, 3, 4, 5];
+    ///
+    /// edit(&mut items, |item| {
+    ///     if *item % 2 == 0 {
+    ///         item.move_to_front();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(items, vec![4, 2, 1, 3, 5]);
+    /// ```
+    pub fn move_to_front(self) {
+        self.move_to(0, |stride, _| stride.moved_back());
+    }
+
+    /// Moves the current item to the end of the list. It won't be visited again.
+    ///
+    /// ```
+    /// # use editer::edit;
+    /// #
+    /// let mut items = vec![1, 2, 3, 4, 5];
+    ///
+    /// edit(&mut items, |item| {
+    ///     if *item % 2 == 0 {
+    ///         item.move_to_end();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(items, vec![1, 3, 5, 2, 4]);
+    /// ```
+    pub fn move_to_end(self) {
+        let (from, end) = (self.index, self.list.len() - 1);
+        self.move_to(end, |stride, len| stride.moved_to_end(len, from));
+    }
+
+    /// Moves the current item `offset` places towards the end of the list, or towards the start
+    /// if `offset` is negative, shifting the items in between by one. It won't be visited again.
+    ///
+    /// Panics if the item would be moved out of bounds.
+    ///
+    /// ```
+    /// # use editer::edit;
+    /// #
+    /// let mut items = vec!["b", "header", "c", "a"];
+    ///
+    /// edit(&mut items, |item| {
+    ///     if item == "b" {
+    ///         item.move_by(1);
+    ///     } else if item == "a" {
+    ///         item.move_by(-2);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(items, vec!["header", "a", "b", "c"]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn move_by(self, offset: isize) {
+        let from = self.index;
+
+        let to = if offset < 0 {
+            from.checked_sub(offset.unsigned_abs())
+        } else {
+            from.checked_add(offset as usize)
+        };
+
+        match to {
+            Some(to) if to < self.list.len() => {
+                self.move_to(to, |stride, len| stride.moved(len, from, to))
+            }
+            _ => panic!("move destination out of bounds"),
+        }
+    }
+
+    fn move_to(self, to: usize, moved: impl FnOnce(&mut Stride, usize)) {
+        if to == self.index {
+            return;
+        }
+
+        self.list.move_item(self.index, to);
+        moved(self.stride, self.list.len());
+        self.stride.record(self.index, Change::Moved(to));
+        self.observer.on_move(self.index, to);
+    }
+
+    pub(crate) fn take(self) -> List::Item {
+        let item = self.list.remove(self.index);
         self.stride.set(0);
+        self.stride.summary().removed += 1;
+        self.stride.record(self.index, Change::Removed);
+        self.observer.on_remove(self.index);
+        item
     }
 }
 
-impl<'list, 'stride, List> Deref for Slot<'list, 'stride, List>
+impl<'list, 'stride, List, Observer> Deref for Slot<'list, 'stride, List, Observer>
 where
     List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
 {
     type Target = List::Item;
 
@@ -200,19 +493,22 @@ where
     }
 }
 
-impl<'list, 'stride, List> DerefMut for Slot<'list, 'stride, List>
+impl<'list, 'stride, List, Observer> DerefMut for Slot<'list, 'stride, List, Observer>
 where
     List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.get_mut()
     }
 }
 
-impl<'list, 'stride, Referent, List> AsRef<Referent> for Slot<'list, 'stride, List>
+impl<'list, 'stride, Referent, List, Observer> AsRef<Referent>
+    for Slot<'list, 'stride, List, Observer>
 where
     Referent: ?Sized,
     List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
     List::Item: AsRef<Referent>,
 {
     fn as_ref(&self) -> &Referent {
@@ -220,10 +516,12 @@ where
     }
 }
 
-impl<'list, 'stride, Referent, List> AsMut<Referent> for Slot<'list, 'stride, List>
+impl<'list, 'stride, Referent, List, Observer> AsMut<Referent>
+    for Slot<'list, 'stride, List, Observer>
 where
     Referent: ?Sized,
     List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
     List::Item: AsMut<Referent>,
 {
     fn as_mut(&mut self) -> &mut Referent {
@@ -231,9 +529,10 @@ where
     }
 }
 
-impl<'list, 'stride, List> PartialEq<List::Item> for Slot<'list, 'stride, List>
+impl<'list, 'stride, List, Observer> PartialEq<List::Item> for Slot<'list, 'stride, List, Observer>
 where
     List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
     List::Item: PartialEq,
 {
     fn eq(&self, other: &List::Item) -> bool {
@@ -241,9 +540,10 @@ where
     }
 }
 
-impl<'list, 'stride, List> PartialOrd<List::Item> for Slot<'list, 'stride, List>
+impl<'list, 'stride, List, Observer> PartialOrd<List::Item> for Slot<'list, 'stride, List, Observer>
 where
     List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
     List::Item: PartialOrd,
 {
     fn partial_cmp(&self, other: &List::Item) -> Option<core::cmp::Ordering> {
@@ -251,9 +551,10 @@ where
     }
 }
 
-impl<'list, 'stride, List> Display for Slot<'list, 'stride, List>
+impl<'list, 'stride, List, Observer> Display for Slot<'list, 'stride, List, Observer>
 where
     List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
     List::Item: Display,
 {
     fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -261,9 +562,10 @@ where
     }
 }
 
-impl<'list, 'stride, List> Debug for Slot<'list, 'stride, List>
+impl<'list, 'stride, List, Observer> Debug for Slot<'list, 'stride, List, Observer>
 where
     List: crate::List + ?Sized,
+    Observer: EditObserver + ?Sized,
     List::Item: Debug,
 {
     fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -275,12 +577,13 @@ where
 mod tests {
     use super::Slot;
     use crate::Stride;
+    use std::collections::VecDeque;
 
     #[test]
     fn deref() {
         let mut list = vec![1, 2, 3, 4, 5];
-        let mut stride = Stride(1);
-        let slot = Slot::new(&mut list, 2, &mut stride);
+        let (mut stride, mut observer) = (Stride::new(), ());
+        let slot = Slot::new(&mut list, 2, &mut stride, &mut observer);
 
         let i: u64 = *slot;
 
@@ -290,8 +593,8 @@ mod tests {
     #[test]
     fn deref_mut() {
         let mut list = vec![1, 2, 3, 4, 5];
-        let mut stride = Stride(1);
-        let mut slot = Slot::new(&mut list, 2, &mut stride);
+        let (mut stride, mut observer) = (Stride::new(), ());
+        let mut slot = Slot::new(&mut list, 2, &mut stride, &mut observer);
 
         *slot = 6;
 
@@ -301,8 +604,8 @@ mod tests {
     #[test]
     fn as_ref() {
         let mut list = vec![1, 2, 3, 4, 5];
-        let mut stride = Stride(1);
-        let slot = Slot::new(&mut list, 2, &mut stride);
+        let (mut stride, mut observer) = (Stride::new(), ());
+        let slot = Slot::new(&mut list, 2, &mut stride, &mut observer);
 
         fn inner(i: &u64) {
             assert_eq!(*i, 3);
@@ -314,8 +617,8 @@ mod tests {
     #[test]
     fn as_mut() {
         let mut list = vec![1, 2, 3, 4, 5];
-        let mut stride = Stride(1);
-        let mut slot = Slot::new(&mut list, 2, &mut stride);
+        let (mut stride, mut observer) = (Stride::new(), ());
+        let mut slot = Slot::new(&mut list, 2, &mut stride, &mut observer);
 
         fn inner(i: &mut u64) {
             *i = 6;
@@ -329,8 +632,8 @@ mod tests {
     #[test]
     fn eq() {
         let mut list = vec![1, 2, 3, 4, 5];
-        let mut stride = Stride(1);
-        let slot = Slot::new(&mut list, 2, &mut stride);
+        let (mut stride, mut observer) = (Stride::new(), ());
+        let slot = Slot::new(&mut list, 2, &mut stride, &mut observer);
 
         assert_eq!(slot, 3);
         assert_ne!(slot, 5);
@@ -339,18 +642,31 @@ mod tests {
     #[test]
     fn cmp() {
         let mut list = vec![1, 2, 3, 4, 5];
-        let mut stride = Stride(1);
-        let slot = Slot::new(&mut list, 2, &mut stride);
+        let (mut stride, mut observer) = (Stride::new(), ());
+        let slot = Slot::new(&mut list, 2, &mut stride, &mut observer);
 
         assert!(slot < 5);
         assert!(!(slot > 5));
     }
 
+    #[test]
+    fn remove_into() {
+        let mut list = vec![1, 2, 3, 4, 5];
+        let mut sink = VecDeque::from([6]);
+        let (mut stride, mut observer) = (Stride::new(), ());
+        let slot = Slot::new(&mut list, 2, &mut stride, &mut observer);
+
+        slot.remove_into(&mut sink);
+
+        assert_eq!(list, vec![1, 2, 4, 5]);
+        assert_eq!(sink, VecDeque::from([6, 3]));
+    }
+
     #[test]
     fn display() {
         let mut list = vec![1, 2, 3, 4, 5];
-        let mut stride = Stride(1);
-        let slot = Slot::new(&mut list, 2, &mut stride);
+        let (mut stride, mut observer) = (Stride::new(), ());
+        let slot = Slot::new(&mut list, 2, &mut stride, &mut observer);
 
         assert_eq!("3", format!("{}", slot));
     }
@@ -358,8 +674,8 @@ mod tests {
     #[test]
     fn debug() {
         let mut list = vec![1, 2, 3, 4, 5];
-        let mut stride = Stride(1);
-        let slot = Slot::new(&mut list, 2, &mut stride);
+        let (mut stride, mut observer) = (Stride::new(), ());
+        let slot = Slot::new(&mut list, 2, &mut stride, &mut observer);
 
         assert_eq!("Slot(3)", format!("{:?}", slot));
     }
@@ -0,0 +1,161 @@
+//! Editing only the items at known positions in a list, without visiting the rest.
+
+use crate::{slot::Slot, List, Stride};
+use core::fmt::{self, Display};
+
+/// Calls `edit` with a [`Slot`] for each item in `items` at one of `indices`, which are positions
+/// in the list as it was before editing. Other items aren't visited.
+///
+/// As the list changes, each index is translated to the current position of the item that was
+/// originally there, so edits made at earlier indices don't affect which items are visited later.
+///
+/// Stops with an error at the first index that's out of range for the original list, keeping the
+/// edits made before it.
+///
+/// Panics if `indices` aren't in strictly ascending order.
+///
+/// ```
+/// use editer::sparse::edit_at;
+///
+/// let mut items = vec![1, 2, 3, 4, 5];
+///
+/// edit_at(&mut items, [0, 2, 3], |item| {
+///     if item == 1 {
+///         item.replace([10, 11]);
+///     } else if item == 3 {
+///         item.remove();
+///     } else {
+///         item.insert_before([0]);
+///     }
+/// })
+/// .unwrap();
+///
+/// assert_eq!(items, vec![10, 11, 2, 0, 4, 5]);
+/// ```
+pub fn edit_at<List>(
+    items: &mut List,
+    indices: impl IntoIterator<Item = usize>,
+    mut edit: impl FnMut(Slot<List>),
+) -> Result<(), OutOfRangeError>
+where
+    List: self::List + ?Sized,
+{
+    let len = items.len();
+    let mut stride = Stride::new();
+
+    // The current position of the item originally at `next`, the first original position that
+    // hasn't been passed yet.
+    let mut index = 0;
+    let mut next = 0;
+
+    for target in indices {
+        if target >= len {
+            return Err(OutOfRangeError { index: target, len });
+        }
+
+        assert!(target >= next, "indices must be in ascending order");
+
+        index = stride.seek(items.len(), index, target - next);
+        edit(Slot::new(items, index, &mut stride, &mut ()));
+        index += stride.advance();
+        next = target + 1;
+    }
+
+    Ok(())
+}
+
+/// The error returned by [`edit_at`] when an index is out of range for the list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfRangeError {
+    /// The index that was out of range.
+    pub index: usize,
+
+    /// The length of the list before editing.
+    pub len: usize,
+}
+
+impl Display for OutOfRangeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "index {} is out of range for a list of length {}",
+            self.index, self.len
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OutOfRangeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_at, OutOfRangeError};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn visiting_only_the_given_indices() {
+        let mut items = VecDeque::from([1, 2, 3, 4, 5]);
+        let mut visited = Vec::new();
+
+        edit_at(&mut items, [1, 4], |item| visited.push(*item)).unwrap();
+
+        assert_eq!(visited, [2, 5]);
+    }
+
+    #[test]
+    fn translating_indices_after_insertions_and_removals() {
+        let mut items = vec![1, 2, 3, 4, 5, 6];
+        let mut visited = Vec::new();
+
+        edit_at(&mut items, [0, 1, 3, 5], |item| {
+            visited.push(*item);
+
+            match *item {
+                1 => item.insert_after([7, 8]),
+                2 => item.remove(),
+                _ => item.replace([0, 0]),
+            }
+        })
+        .unwrap();
+
+        assert_eq!(visited, [1, 2, 4, 6]);
+        assert_eq!(items, vec![1, 7, 8, 3, 0, 0, 5, 0, 0]);
+    }
+
+    #[test]
+    fn translating_indices_after_moves() {
+        let mut items = vec![1, 2, 3, 4, 5, 6];
+        let mut visited = Vec::new();
+
+        edit_at(&mut items, [0, 2, 3, 5], |item| {
+            visited.push(*item);
+
+            match *item {
+                1 => item.move_by(2),
+                3 => item.move_to_front(),
+                4 => item.move_to_end(),
+                _ => {}
+            }
+        })
+        .unwrap();
+
+        assert_eq!(visited, [1, 3, 4, 6]);
+        assert_eq!(items, vec![3, 2, 1, 5, 6, 4]);
+    }
+
+    #[test]
+    fn reporting_an_index_out_of_range() {
+        let mut items = vec![1, 2, 3];
+
+        let result = edit_at(&mut items, [0, 3], |item| item.remove());
+
+        assert_eq!(result, Err(OutOfRangeError { index: 3, len: 3 }));
+        assert_eq!(items, vec![2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "indices must be in ascending order")]
+    fn editing_unsorted_indices() {
+        let _ = edit_at(&mut vec![1, 2, 3], [2, 1], |_| {});
+    }
+}
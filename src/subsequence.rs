@@ -0,0 +1,225 @@
+//! Finding and replacing runs of items in a [`List`], like [`str::replace`] does for strings.
+
+use crate::List;
+use alloc::vec::Vec;
+
+/// Replaces the first occurrence of `pattern` in `list` with `replacement`, returning the index
+/// at which it occurred, or `None` if it didn't occur.
+///
+/// An empty `pattern` never occurs, so the list is left alone.
+///
+/// ```
+/// use editer::subsequence::replace_subsequence;
+///
+/// let mut items = vec![1, 2, 3, 1, 2, 3];
+///
+/// assert_eq!(replace_subsequence(&mut items, &[2, 3], &[4]), Some(1));
+/// assert_eq!(items, vec![1, 4, 1, 2, 3]);
+///
+/// assert_eq!(replace_subsequence(&mut items, &[3, 2], &[4]), None);
+/// ```
+pub fn replace_subsequence<List>(
+    list: &mut List,
+    pattern: &[List::Item],
+    replacement: &[List::Item],
+) -> Option<usize>
+where
+    List: self::List + ?Sized,
+    List::Item: PartialEq + Clone,
+{
+    let matcher = Matcher::new(pattern);
+    let index = matcher.find(list, 0)?;
+    list.replace_range(index..index + pattern.len(), replacement.iter().cloned());
+    Some(index)
+}
+
+/// Replaces every non-overlapping occurrence of `pattern` in `list` with `replacement`, from
+/// first to last, returning the number of occurrences replaced.
+///
+/// Occurrences of `pattern` that only appear once replacements have been made aren't replaced. An
+/// empty `pattern` never occurs, so the list is left alone.
+///
+/// ```
+/// use editer::subsequence::replace_all_subsequences;
+///
+/// let mut tokens = vec!["a", "+", "0", "+", "0", "+", "b"];
+///
+/// assert_eq!(replace_all_subsequences(&mut tokens, &["+", "0"], &[]), 2);
+/// assert_eq!(tokens, vec!["a", "+", "b"]);
+/// ```
+pub fn replace_all_subsequences<List>(
+    list: &mut List,
+    pattern: &[List::Item],
+    replacement: &[List::Item],
+) -> usize
+where
+    List: self::List + ?Sized,
+    List::Item: PartialEq + Clone,
+{
+    let matcher = Matcher::new(pattern);
+    let mut start = 0;
+    let mut count = 0;
+
+    while let Some(index) = matcher.find(list, start) {
+        list.replace_range(index..index + pattern.len(), replacement.iter().cloned());
+        start = index + replacement.len();
+        count += 1;
+    }
+
+    count
+}
+
+/// Searches for a pattern using the Knuth–Morris–Pratt algorithm, which never backtracks over
+/// the items in the list. An empty pattern matches nothing.
+struct Matcher<'pattern, Item> {
+    pattern: &'pattern [Item],
+
+    /// For each prefix of the pattern, the length of its longest proper prefix that's also a
+    /// suffix of it.
+    fallback: Vec<usize>,
+}
+
+impl<'pattern, Item> Matcher<'pattern, Item>
+where
+    Item: PartialEq,
+{
+    fn new(pattern: &'pattern [Item]) -> Matcher<'pattern, Item> {
+        let mut fallback = alloc::vec![0; pattern.len()];
+        let mut matched = 0;
+
+        for index in 1..pattern.len() {
+            while matched > 0 && pattern[index] != pattern[matched] {
+                matched = fallback[matched - 1];
+            }
+
+            if pattern[index] == pattern[matched] {
+                matched += 1;
+            }
+
+            fallback[index] = matched;
+        }
+
+        Matcher { pattern, fallback }
+    }
+
+    /// Returns the index of the first occurrence of the pattern in `list` at or after `start`.
+    fn find<List>(&self, list: &List, start: usize) -> Option<usize>
+    where
+        List: self::List<Item = Item> + ?Sized,
+    {
+        if self.pattern.is_empty() {
+            return None;
+        }
+
+        let mut matched = 0;
+
+        for index in start..list.len() {
+            let item = list.index(index);
+
+            while matched > 0 && *item != self.pattern[matched] {
+                matched = self.fallback[matched - 1];
+            }
+
+            if *item == self.pattern[matched] {
+                matched += 1;
+            }
+
+            if matched == self.pattern.len() {
+                return Some(index + 1 - matched);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{replace_all_subsequences, replace_subsequence};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn replacing_a_pattern_that_overlaps_a_partial_match() {
+        let mut items = vec![1, 1, 2, 1, 1, 1, 2, 3];
+
+        assert_eq!(
+            replace_subsequence(&mut items, &[1, 1, 2, 3], &[0]),
+            Some(4)
+        );
+        assert_eq!(items, vec![1, 1, 2, 1, 0]);
+    }
+
+    #[test]
+    fn replacing_a_pattern_at_either_end() {
+        let mut items = VecDeque::from(['a', 'b', 'c', 'a', 'b']);
+
+        assert_eq!(
+            replace_all_subsequences(&mut items, &['a', 'b'], &['x', 'y', 'z']),
+            2
+        );
+        assert_eq!(items, VecDeque::from(['x', 'y', 'z', 'c', 'x', 'y', 'z']));
+    }
+
+    #[test]
+    fn replacing_non_overlapping_occurrences() {
+        let mut items = vec![1, 1, 1, 1, 1];
+
+        assert_eq!(replace_all_subsequences(&mut items, &[1, 1], &[2]), 2);
+        assert_eq!(items, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn not_replacing_occurrences_in_replacements() {
+        let mut items = vec![1, 2];
+
+        assert_eq!(replace_all_subsequences(&mut items, &[1], &[1, 1]), 1);
+        assert_eq!(items, vec![1, 1, 2]);
+    }
+
+    #[test]
+    fn replacing_a_pattern_longer_than_the_list() {
+        let mut items = vec![1, 2];
+
+        assert_eq!(replace_subsequence(&mut items, &[1, 2, 3], &[]), None);
+        assert_eq!(replace_all_subsequences(&mut items, &[1, 2, 3], &[]), 0);
+        assert_eq!(items, vec![1, 2]);
+    }
+
+    #[test]
+    fn matching_every_pattern_in_every_short_list() {
+        // Compare against a naive search over every list and pattern of up to 4 items from a
+        // two-letter alphabet.
+        let sequences = (0..=4).flat_map(|len| {
+            (0..1 << len).map(move |bits| (0..len).map(|bit| bits >> bit & 1).collect::<Vec<_>>())
+        });
+
+        let sequences: Vec<Vec<i32>> = sequences.collect();
+
+        for list in &sequences {
+            for pattern in sequences.iter().filter(|pattern| !pattern.is_empty()) {
+                let expected = list
+                    .windows(pattern.len())
+                    .position(|window| window == &pattern[..]);
+
+                let mut actual = list.clone();
+
+                assert_eq!(
+                    replace_subsequence(&mut actual, pattern, &[]),
+                    expected,
+                    "finding {:?} in {:?}",
+                    pattern,
+                    list
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn replacing_an_empty_pattern() {
+        let mut items = vec![1];
+
+        assert_eq!(replace_subsequence(&mut items, &[], &[2]), None);
+        assert_eq!(replace_all_subsequences(&mut items, &[], &[2]), 0);
+        assert_eq!(items, vec![1]);
+    }
+}
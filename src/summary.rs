@@ -0,0 +1,149 @@
+//! Summarizing the changes made by an edit.
+
+/// Describes the changes made to a list by [`edit_with_summary`](crate::edit_with_summary) or
+/// [`try_edit_with_summary`](crate::try_edit_with_summary).
+///
+/// Only changes to the structure of the list made through a [`Slot`](crate::slot::Slot) are
+/// counted. Mutating an item in place (e.g. via [`DerefMut`](core::ops::DerefMut)) isn't.
+///
+/// The counts are kept during every edit, not just the ones that return a summary, at the cost of
+/// an addition per item visited and per change made.
+///
+/// More counts may be added in future, so `EditSummary` can't be constructed outside this crate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct EditSummary {
+    /// The number of items visited.
+    pub visited: usize,
+
+    /// The number of items inserted into the list.
+    pub inserted: usize,
+
+    /// The number of visited items removed.
+    pub removed: usize,
+
+    /// The number of visited items replaced, regardless of how many items replaced them.
+    pub replaced: usize,
+
+    /// The number of visited items moved elsewhere in the list.
+    pub moved: usize,
+
+    /// The length of the list after the edit.
+    pub final_len: usize,
+}
+
+impl EditSummary {
+    /// Returns `true` if the structure of the list was changed at all.
+    ///
+    /// ```
+    /// use editer::edit_with_summary;
+    ///
+    /// let mut items = vec![1, 2, 3];
+    ///
+    /// assert!(!edit_with_summary(&mut items, |mut item| *item += 1).is_changed());
+    /// assert!(edit_with_summary(&mut items, |item| item.remove()).is_changed());
+    /// ```
+    pub fn is_changed(&self) -> bool {
+        self.inserted > 0 || self.removed > 0 || self.replaced > 0 || self.moved > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EditSummary;
+    use crate::{edit_with_summary, try_edit_with_summary};
+
+    #[test]
+    fn summarizing_every_kind_of_change() {
+        let mut items = vec![1, 2, 3, 4, 5];
+
+        let summary = edit_with_summary(&mut items, |mut item| {
+            if item == 1 {
+                item.insert_before([6, 7]);
+            } else if item == 2 {
+                item.insert_after([8]);
+            } else if item == 3 {
+                *item = 9;
+            } else if item == 4 {
+                item.replace([10, 11, 12]);
+            } else if item == 5 {
+                item.remove();
+            }
+        });
+
+        assert_eq!(items, vec![6, 7, 1, 2, 8, 9, 10, 11, 12]);
+        assert_eq!(
+            summary,
+            EditSummary {
+                visited: 5,
+                inserted: 3,
+                removed: 1,
+                replaced: 1,
+                moved: 0,
+                final_len: 9,
+            }
+        );
+    }
+
+    #[test]
+    fn summarizing_an_edit_of_an_empty_list() {
+        let mut items: Vec<i32> = vec![];
+
+        let summary = edit_with_summary(&mut items, |item| item.remove());
+
+        assert_eq!(summary, EditSummary::default());
+        assert!(!summary.is_changed());
+    }
+
+    #[test]
+    fn summarizing_a_successful_fallible_edit() {
+        let mut items = vec![1, 2, 3];
+
+        let result = try_edit_with_summary(&mut items, |item| {
+            if item == 2 {
+                item.replace([]);
+            }
+
+            Ok::<_, ()>(())
+        });
+
+        assert_eq!(
+            result,
+            Ok(EditSummary {
+                visited: 3,
+                inserted: 0,
+                removed: 0,
+                replaced: 1,
+                moved: 0,
+                final_len: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn summarizing_moves() {
+        let mut items = vec![1, 2, 3, 4];
+
+        let summary = edit_with_summary(&mut items, |item| {
+            if item == 1 {
+                item.move_to_end();
+            } else if item == 3 {
+                item.move_to_front();
+            }
+        });
+
+        assert_eq!(items, vec![3, 2, 4, 1]);
+        assert_eq!(
+            summary,
+            EditSummary {
+                visited: 4,
+                inserted: 0,
+                removed: 0,
+                replaced: 0,
+                moved: 2,
+                final_len: 4,
+            }
+        );
+        assert!(summary.is_changed());
+    }
+}
@@ -0,0 +1,637 @@
+//! Editing trees of nodes whose children are [`List`]s, one node at a time.
+
+use crate::{observer::EditObserver, slot::Slot, List, Stride};
+use alloc::vec::Vec;
+use core::{
+    fmt::Debug,
+    iter, mem,
+    ops::{Deref, DerefMut},
+};
+
+/// A node in a tree, whose children are stored in a [`List`].
+///
+/// ```
+/// use editer::tree::Tree;
+///
+/// struct Element {
+///     tag: &'static str,
+///     children: Vec<Element>,
+/// }
+///
+/// impl Tree for Element {
+///     type Children = Vec<Element>;
+///
+///     fn children(&self) -> &Vec<Element> {
+///         &self.children
+///     }
+///
+///     fn children_mut(&mut self) -> &mut Vec<Element> {
+///         &mut self.children
+///     }
+/// }
+/// ```
+pub trait Tree: Sized {
+    /// The list in which the node's children are stored.
+    type Children: List<Item = Self>;
+
+    /// Returns a shared reference to the node's children.
+    fn children(&self) -> &Self::Children;
+
+    /// Returns a mutable reference to the node's children.
+    fn children_mut(&mut self) -> &mut Self::Children;
+}
+
+/// The order in which [`edit_tree`] visits the nodes of a tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Order {
+    /// Visit each node before its children. Changes made to a node determine which children are
+    /// visited afterwards.
+    PreOrder,
+
+    /// Visit each node after its children. Changes made to the children are visible when the node
+    /// is visited.
+    PostOrder,
+}
+
+/// Walks the trees in `nodes` depth-first, calling `edit` with a [`TreeSlot`] for each node. The
+/// `TreeSlot` allows accessing the current node and/or updating the list of its siblings at its
+/// position, as a [`Slot`] does.
+///
+/// As with [`edit`](crate::edit), nodes inserted or used as replacements aren't visited.
+///
+/// ```
+/// use editer::tree::{edit_tree, Order, Tree};
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # struct Node(&'static str, Vec<Node>);
+/// #
+/// # impl Tree for Node {
+/// #     type Children = Vec<Node>;
+/// #
+/// #     fn children(&self) -> &Vec<Node> {
+/// #         &self.1
+/// #     }
+/// #
+/// #     fn children_mut(&mut self) -> &mut Vec<Node> {
+/// #         &mut self.1
+/// #     }
+/// # }
+///
+/// // (a (b c) (d))
+/// let mut nodes = vec![Node("a", vec![Node("b", vec![Node("c", vec![])]), Node("d", vec![])])];
+///
+/// edit_tree(&mut nodes, Order::PreOrder, |node| {
+///     if node.0 == "b" {
+///         node.unwrap();
+///     } else if node.0 == "d" {
+///         node.remove();
+///     }
+/// });
+///
+/// // (a c)
+/// assert_eq!(nodes, vec![Node("a", vec![Node("c", vec![])])]);
+/// ```
+pub fn edit_tree<List>(nodes: &mut List, order: Order, mut edit: impl FnMut(TreeSlot<List>))
+where
+    List: self::List + ?Sized,
+    List::Item: Tree<Children = List>,
+{
+    // The lists of siblings being walked, from the outermost to the innermost. Keeping them on
+    // an explicit stack rather than recursing means deep trees can't overflow the call stack.
+    let mut stack = alloc::vec![Frame::new(nodes, 0)];
+
+    while let Some(frame) = stack.last_mut() {
+        // SAFETY: Only the top frame's list is accessed, and nothing borrowed from it outlives
+        // this iteration. See `Frame`.
+        let nodes = unsafe { &mut *frame.nodes };
+        let index = frame.index;
+
+        if index >= nodes.len() - frame.stride.tail() {
+            stack.pop();
+            continue;
+        }
+
+        if !frame.walked_children {
+            if frame.stride.skip(nodes.len() - index) {
+                frame.index += 1;
+                continue;
+            }
+
+            if order == Order::PostOrder {
+                frame.walked_children = true;
+                let children = Frame::new(nodes.index_mut(index).children_mut(), frame.depth + 1);
+                stack.push(children);
+                continue;
+            }
+        }
+
+        frame.walked_children = false;
+
+        let depth = frame.depth;
+        let mut descend = Descend::Into(index);
+
+        edit(TreeSlot {
+            slot: Slot::new(&mut *nodes, index, &mut frame.stride, &mut ()),
+            order,
+            depth,
+            descend: &mut descend,
+        });
+
+        frame.index += frame.stride.advance();
+
+        if order == Order::PreOrder {
+            let children = match descend {
+                Descend::Into(index) => {
+                    Frame::new(nodes.index_mut(index).children_mut(), depth + 1)
+                }
+                Descend::IntoWrapped(index) => {
+                    let children = nodes.index_mut(index).children_mut();
+                    let last = children.len() - 1;
+                    Frame::new(children.index_mut(last).children_mut(), depth + 2)
+                }
+                Descend::Nowhere => continue,
+            };
+
+            stack.push(children);
+        }
+    }
+}
+
+/// A list of siblings being walked by [`edit_tree`].
+///
+/// `nodes` points to either the list passed to `edit_tree` or the children of a node in the list
+/// of the frame below. A frame's list is only accessed while it's on top of the stack, so the
+/// frames below it, and the nodes owning their children, are left alone until it's popped.
+struct Frame<List>
+where
+    List: self::List + ?Sized,
+{
+    nodes: *mut List,
+    stride: Stride,
+    index: usize,
+    depth: usize,
+
+    /// Whether the children of the node at `index` have been walked, in a post-order walk.
+    walked_children: bool,
+}
+
+impl<List> Frame<List>
+where
+    List: self::List + ?Sized,
+{
+    fn new(nodes: &mut List, depth: usize) -> Frame<List> {
+        Frame {
+            nodes,
+            stride: Stride::new(),
+            index: 0,
+            depth,
+            walked_children: false,
+        }
+    }
+}
+
+/// Where the children of the node just visited in a pre-order walk ended up.
+enum Descend {
+    /// The node is at the given position among its siblings.
+    Into(usize),
+
+    /// The node is the last child of the node at the given position among its former siblings.
+    IntoWrapped(usize),
+
+    /// The node is no longer in the tree.
+    Nowhere,
+}
+
+/// Represents the current node in an [`edit_tree`].
+///
+/// If the `TreeSlot` is dropped without calling any of its methods that take `self`, the node
+/// stays where it is.
+pub struct TreeSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: Tree<Children = List>,
+{
+    slot: Slot<'list, 'stride, List>,
+    order: Order,
+    depth: usize,
+    descend: &'stride mut Descend,
+}
+
+impl<'list, 'stride, List> TreeSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: Tree<Children = List>,
+{
+    /// Returns a shared reference to the current node.
+    pub fn get(&self) -> &List::Item {
+        self.slot.get()
+    }
+
+    /// Returns a mutable reference to the current node.
+    pub fn get_mut(&mut self) -> &mut List::Item {
+        self.slot.get_mut()
+    }
+
+    /// Returns the number of ancestors of the current node, which is zero for the nodes in the
+    /// list passed to [`edit_tree`].
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Inserts zero or more `nodes` before the current node, among its siblings.
+    pub fn insert_before<Nodes>(self, nodes: Nodes)
+    where
+        Nodes: IntoIterator,
+        Nodes::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
+    {
+        let nodes = nodes.into_iter();
+
+        if let Descend::Into(index) = self.descend {
+            *index += nodes.len();
+        }
+
+        self.slot.insert_before(nodes);
+    }
+
+    /// Inserts zero or more `nodes` after the current node, among its siblings.
+    pub fn insert_after<Nodes>(self, nodes: Nodes)
+    where
+        Nodes: IntoIterator,
+        Nodes::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
+    {
+        self.slot.insert_after(nodes);
+    }
+
+    /// Replaces the current node, along with its descendants, with zero or more `nodes`.
+    pub fn replace<Nodes>(self, nodes: Nodes)
+    where
+        Nodes: IntoIterator,
+        Nodes::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
+    {
+        *self.descend = Descend::Nowhere;
+        self.slot.replace(nodes);
+    }
+
+    /// Removes the current node, along with its descendants.
+    pub fn remove(self) {
+        *self.descend = Descend::Nowhere;
+        self.slot.remove();
+    }
+
+    /// Replaces the current node with its children.
+    ///
+    /// In a pre-order walk, the children are visited next, in their new place. In a post-order
+    /// walk, they've already been visited, and aren't visited again.
+    ///
+    /// ```
+    /// # use editer::tree::{edit_tree, Order, Tree};
+    /// #
+    /// # #[derive(Debug, PartialEq)]
+    /// # struct Node(&'static str, Vec<Node>);
+    /// #
+    /// # impl Tree for Node {
+    /// #     type Children = Vec<Node>;
+    /// #
+    /// #     fn children(&self) -> &Vec<Node> {
+    /// #         &self.1
+    /// #     }
+    /// #
+    /// #     fn children_mut(&mut self) -> &mut Vec<Node> {
+    /// #         &mut self.1
+    /// #     }
+    /// # }
+    /// #
+    /// // (span "a" (span "b"))
+    /// let mut nodes = vec![Node(
+    ///     "span",
+    ///     vec![Node("a", vec![]), Node("span", vec![Node("b", vec![])])],
+    /// )];
+    ///
+    /// edit_tree(&mut nodes, Order::PreOrder, |node| {
+    ///     if node.0 == "span" {
+    ///         node.unwrap();
+    ///     }
+    /// });
+    ///
+    /// // "a" "b"
+    /// assert_eq!(nodes, vec![Node("a", vec![]), Node("b", vec![])]);
+    /// ```
+    pub fn unwrap(self) {
+        *self.descend = Descend::Nowhere;
+
+        let (list, index, stride, observer) = self.slot.into_parts();
+        let mut node = Slot::new(list, index, &mut *stride, &mut *observer).take();
+
+        // Removing the children from the back is cheap for any list, and they can then be
+        // inserted all at once.
+        let children = node.children_mut();
+        let children: Vec<_> = (0..children.len())
+            .rev()
+            .map(|index| children.remove(index))
+            .collect();
+        let count = children.len();
+
+        list.insert(index, children.into_iter().rev());
+        stride.summary().inserted += count;
+        observer.on_insert(index, count);
+
+        if self.order == Order::PostOrder {
+            stride.set(count);
+        }
+    }
+
+    /// Replaces the current node with `parent`, and makes the current node the last child of
+    /// `parent`.
+    ///
+    /// `parent` isn't visited. In a pre-order walk, the descendants of the current node are
+    /// visited afterwards, as if it hadn't been wrapped.
+    ///
+    /// ```
+    /// # use editer::tree::{edit_tree, Order, Tree};
+    /// #
+    /// # #[derive(Debug, PartialEq)]
+    /// # struct Node(&'static str, Vec<Node>);
+    /// #
+    /// # impl Tree for Node {
+    /// #     type Children = Vec<Node>;
+    /// #
+    /// #     fn children(&self) -> &Vec<Node> {
+    /// #         &self.1
+    /// #     }
+    /// #
+    /// #     fn children_mut(&mut self) -> &mut Vec<Node> {
+    /// #         &mut self.1
+    /// #     }
+    /// # }
+    /// #
+    /// let mut nodes = vec![Node("img", vec![]), Node("p", vec![])];
+    ///
+    /// edit_tree(&mut nodes, Order::PostOrder, |node| {
+    ///     if node.0 == "img" {
+    ///         node.wrap(Node("figure", vec![Node("caption", vec![])]));
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(
+    ///     nodes,
+    ///     vec![
+    ///         Node("figure", vec![Node("caption", vec![]), Node("img", vec![])]),
+    ///         Node("p", vec![])
+    ///     ]
+    /// );
+    /// ```
+    pub fn wrap(mut self, parent: List::Item) {
+        if let Descend::Into(index) = *self.descend {
+            *self.descend = Descend::IntoWrapped(index);
+        }
+
+        let node = mem::replace(self.slot.get_mut(), parent);
+        let children = self.slot.get_mut().children_mut();
+        children.insert(children.len(), iter::once(node));
+    }
+}
+
+impl<'list, 'stride, List> Deref for TreeSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: Tree<Children = List>,
+{
+    type Target = List::Item;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<'list, 'stride, List> DerefMut for TreeSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: Tree<Children = List>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.get_mut()
+    }
+}
+
+impl<'list, 'stride, List> Debug for TreeSlot<'list, 'stride, List>
+where
+    List: self::List + ?Sized,
+    List::Item: Tree<Children = List> + Debug,
+{
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter
+            .debug_struct("TreeSlot")
+            .field("node", self.get())
+            .field("depth", &self.depth)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_tree, Order, Tree};
+    use std::collections::VecDeque;
+
+    #[derive(Debug, PartialEq)]
+    struct Node(&'static str, Vec<Node>);
+
+    impl Tree for Node {
+        type Children = Vec<Node>;
+
+        fn children(&self) -> &Vec<Node> {
+            &self.1
+        }
+
+        fn children_mut(&mut self) -> &mut Vec<Node> {
+            &mut self.1
+        }
+    }
+
+    fn leaf(name: &'static str) -> Node {
+        Node(name, vec![])
+    }
+
+    fn sample() -> Vec<Node> {
+        vec![
+            Node("a", vec![Node("b", vec![leaf("c")]), leaf("d")]),
+            leaf("e"),
+        ]
+    }
+
+    #[test]
+    fn walking_in_pre_order() {
+        let mut nodes = sample();
+        let mut visited = Vec::new();
+
+        edit_tree(&mut nodes, Order::PreOrder, |node| {
+            visited.push((node.0, node.depth()))
+        });
+
+        assert_eq!(visited, [("a", 0), ("b", 1), ("c", 2), ("d", 1), ("e", 0)]);
+    }
+
+    #[test]
+    fn walking_in_post_order() {
+        let mut nodes = sample();
+        let mut visited = Vec::new();
+
+        edit_tree(&mut nodes, Order::PostOrder, |node| visited.push(node.0));
+
+        assert_eq!(visited, ["c", "b", "d", "a", "e"]);
+    }
+
+    #[test]
+    fn descending_into_nodes_after_inserting_siblings() {
+        let mut nodes = sample();
+        let mut visited = Vec::new();
+
+        edit_tree(&mut nodes, Order::PreOrder, |node| {
+            visited.push(node.0);
+
+            if node.0 == "a" {
+                node.insert_before([leaf("x")]);
+            } else if node.0 == "b" {
+                node.insert_after([Node("y", vec![leaf("z")])]);
+            }
+        });
+
+        assert_eq!(visited, ["a", "b", "c", "d", "e"]);
+        assert_eq!(
+            nodes,
+            vec![
+                leaf("x"),
+                Node(
+                    "a",
+                    vec![
+                        Node("b", vec![leaf("c")]),
+                        Node("y", vec![leaf("z")]),
+                        leaf("d")
+                    ]
+                ),
+                leaf("e")
+            ]
+        );
+    }
+
+    #[test]
+    fn not_descending_into_replaced_nodes() {
+        let mut nodes = sample();
+        let mut visited = Vec::new();
+
+        edit_tree(&mut nodes, Order::PreOrder, |node| {
+            visited.push(node.0);
+
+            if node.0 == "b" {
+                node.replace([Node("f", vec![leaf("g")])]);
+            }
+        });
+
+        assert_eq!(visited, ["a", "b", "d", "e"]);
+        assert_eq!(
+            nodes,
+            vec![
+                Node("a", vec![Node("f", vec![leaf("g")]), leaf("d")]),
+                leaf("e")
+            ]
+        );
+    }
+
+    #[test]
+    fn unwrapping_nodes_in_post_order() {
+        let mut nodes = sample();
+        let mut visited = Vec::new();
+
+        edit_tree(&mut nodes, Order::PostOrder, |node| {
+            visited.push(node.0);
+
+            if node.0 == "a" || node.0 == "b" {
+                node.unwrap();
+            }
+        });
+
+        assert_eq!(visited, ["c", "b", "d", "a", "e"]);
+        assert_eq!(nodes, vec![leaf("c"), leaf("d"), leaf("e")]);
+    }
+
+    #[test]
+    fn descending_into_wrapped_nodes() {
+        let mut nodes = sample();
+        let mut visited = Vec::new();
+
+        edit_tree(&mut nodes, Order::PreOrder, |node| {
+            visited.push((node.0, node.depth()));
+
+            if node.0 == "b" {
+                node.wrap(leaf("w"));
+            }
+        });
+
+        assert_eq!(visited, [("a", 0), ("b", 1), ("c", 3), ("d", 1), ("e", 0)]);
+        assert_eq!(
+            nodes,
+            vec![
+                Node(
+                    "a",
+                    vec![Node("w", vec![Node("b", vec![leaf("c")])]), leaf("d")]
+                ),
+                leaf("e")
+            ]
+        );
+    }
+
+    #[test]
+    fn walking_a_deep_tree() {
+        const DEPTH: usize = 100_000;
+
+        let mut node = leaf("x");
+
+        for _ in 0..DEPTH {
+            node = Node("x", vec![node]);
+        }
+
+        let mut nodes = vec![node];
+        let (mut visited, mut deepest) = (0, 0);
+
+        edit_tree(&mut nodes, Order::PostOrder, |node| {
+            visited += 1;
+            deepest = deepest.max(node.depth());
+        });
+
+        assert_eq!((visited, deepest), (DEPTH + 1, DEPTH));
+
+        // Flatten the tree before dropping it, since dropping it whole would recurse too.
+        edit_tree(&mut nodes, Order::PreOrder, |node| node.unwrap());
+
+        assert!(nodes.is_empty());
+    }
+
+    #[test]
+    fn editing_nodes_with_other_lists_of_children() {
+        #[derive(Debug, PartialEq)]
+        struct Branch(u8, VecDeque<Branch>);
+
+        impl Tree for Branch {
+            type Children = VecDeque<Branch>;
+
+            fn children(&self) -> &VecDeque<Branch> {
+                &self.1
+            }
+
+            fn children_mut(&mut self) -> &mut VecDeque<Branch> {
+                &mut self.1
+            }
+        }
+
+        let mut nodes = VecDeque::from([Branch(
+            1,
+            VecDeque::from([Branch(2, VecDeque::new()), Branch(3, VecDeque::new())]),
+        )]);
+
+        edit_tree(&mut nodes, Order::PostOrder, |mut node| {
+            let sum: u8 = node.children().iter().map(|child| child.0).sum();
+            node.0 += sum;
+        });
+
+        assert_eq!(nodes[0].0, 6);
+    }
+}
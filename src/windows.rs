@@ -0,0 +1,359 @@
+//! Editing a list through a sliding window of overlapping neighbors.
+
+use crate::{range::RangeSlot, List};
+use core::{
+    fmt::{self, Debug, Display},
+    ops::{Deref, DerefMut},
+};
+
+/// The number of rewrites [`edit_windows`] allows per item in the original list, plus one, before
+/// giving up.
+pub const DEFAULT_REWRITES_PER_ITEM: usize = 16;
+
+/// Slides a window of `size` consecutive items over `items`, calling `edit` with a
+/// [`WindowSlot`] for each position. The `WindowSlot` allows accessing the items in the window
+/// and/or replacing them all at once.
+///
+/// After a window is replaced, the window backs up `size - 1` items, so that it covers the first
+/// replacement item and the items before it. This catches opportunities to rewrite that the
+/// replacement created with the items before it. Otherwise, the window moves on by one item.
+///
+/// A sequence of rewrites may never end, e.g. if a replacement matches the pattern it replaced.
+/// To guard against this, `edit_windows` stops with an error instead of making more than
+/// `(len + 1) * DEFAULT_REWRITES_PER_ITEM` rewrites, where `len` is the length of the original
+/// list. Use [`edit_windows_with_limit`] to set a different limit. Otherwise, returns the number
+/// of rewrites.
+///
+/// Panics if `size` is zero.
+///
+/// ```
+/// use editer::windows::edit_windows;
+///
+/// let mut instructions = vec!["push a", "push b", "pop b", "pop a", "ret"];
+///
+/// let rewrites = edit_windows(&mut instructions, 2, |window| {
+///     let pushed = window[0].strip_prefix("push ");
+///
+///     if pushed.is_some() && pushed == window[1].strip_prefix("pop ") {
+///         window.remove();
+///     }
+/// });
+///
+/// assert_eq!(instructions, vec!["ret"]);
+/// assert_eq!(rewrites, Ok(2));
+/// ```
+pub fn edit_windows<List>(
+    items: &mut List,
+    size: usize,
+    edit: impl FnMut(WindowSlot<List>),
+) -> Result<usize, RewriteLimitError>
+where
+    List: self::List + ?Sized,
+{
+    let limit = (items.len().saturating_add(1)).saturating_mul(DEFAULT_REWRITES_PER_ITEM);
+    edit_windows_with_limit(items, size, limit, edit)
+}
+
+/// Like [`edit_windows`], but stops with an error instead of making more than `limit` rewrites,
+/// rather than the default. The rewrite that would have gone over the limit isn't made.
+///
+/// ```
+/// use editer::windows::{edit_windows_with_limit, RewriteLimitError};
+///
+/// let mut items = vec![1, 2];
+///
+/// let result = edit_windows_with_limit(&mut items, 2, 3, |window| {
+///     let swapped = [window[1], window[0]];
+///     window.replace(swapped);
+/// });
+///
+/// assert_eq!(result, Err(RewriteLimitError { limit: 3 }));
+/// assert_eq!(items, vec![2, 1]);
+/// ```
+pub fn edit_windows_with_limit<List>(
+    items: &mut List,
+    size: usize,
+    limit: usize,
+    mut edit: impl FnMut(WindowSlot<List>),
+) -> Result<usize, RewriteLimitError>
+where
+    List: self::List + ?Sized,
+{
+    assert!(size != 0, "window size must be non-zero");
+
+    let mut index = 0;
+    let mut rewrites = 0;
+
+    while index + size <= items.len() {
+        let allowed = rewrites < limit;
+        let mut rewritten = false;
+        edit(WindowSlot::new(items, index, size, allowed, &mut rewritten));
+
+        if rewritten {
+            if !allowed {
+                return Err(RewriteLimitError { limit });
+            }
+
+            rewrites += 1;
+            index = index.saturating_sub(size - 1);
+        } else {
+            index += 1;
+        }
+    }
+
+    Ok(rewrites)
+}
+
+/// The error returned by [`edit_windows`] when it gives up after too many rewrites.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RewriteLimitError {
+    /// The number of rewrites allowed, all of which were made before giving up.
+    pub limit: usize,
+}
+
+impl Display for RewriteLimitError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "gave up rewriting after {} rewrites", self.limit)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RewriteLimitError {}
+
+/// Represents the current window of consecutive items in an [`edit_windows`].
+///
+/// Dereferences to a [`RangeSlot`] for accessing the items in the window, which always number the
+/// window size. Mutating items in place doesn't count as a rewrite, so the window moves on by one
+/// item afterwards.
+pub struct WindowSlot<'list, 'rewritten, List>
+where
+    List: self::List + ?Sized,
+{
+    range: RangeSlot<'list, List>,
+
+    /// Whether a rewrite is within the limit, and is to be applied rather than just recorded.
+    allowed: bool,
+
+    rewritten: &'rewritten mut bool,
+}
+
+impl<'list, 'rewritten, List> WindowSlot<'list, 'rewritten, List>
+where
+    List: self::List + ?Sized,
+{
+    pub(crate) fn new(
+        list: &'list mut List,
+        index: usize,
+        len: usize,
+        allowed: bool,
+        rewritten: &'rewritten mut bool,
+    ) -> WindowSlot<'list, 'rewritten, List> {
+        WindowSlot {
+            range: RangeSlot::new(list, index, len),
+            allowed,
+            rewritten,
+        }
+    }
+
+    /// Replaces the items in the window with zero or more `items`, and backs the window up.
+    ///
+    /// ```
+    /// # use editer::windows::edit_windows;
+    /// #
+    /// let mut items = vec![1, 1, 2, 3];
+    ///
+    /// edit_windows(&mut items, 2, |window| {
+    ///     if window[0] == window[1] {
+    ///         let sum = window[0] + window[1];
+    ///         window.replace([sum]);
+    ///     }
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(items, vec![4, 3]);
+    /// ```
+    pub fn replace<Items>(self, items: Items)
+    where
+        Items: IntoIterator,
+        Items::IntoIter: Iterator<Item = List::Item> + ExactSizeIterator,
+    {
+        *self.rewritten = true;
+
+        if self.allowed {
+            self.range.replace(items.into_iter());
+        }
+    }
+
+    /// Removes the items in the window, and backs the window up.
+    pub fn remove(self) {
+        *self.rewritten = true;
+
+        if self.allowed {
+            self.range.remove();
+        }
+    }
+}
+
+impl<'list, 'rewritten, List> Deref for WindowSlot<'list, 'rewritten, List>
+where
+    List: self::List + ?Sized,
+{
+    type Target = RangeSlot<'list, List>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.range
+    }
+}
+
+impl<'list, 'rewritten, List> DerefMut for WindowSlot<'list, 'rewritten, List>
+where
+    List: self::List + ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.range
+    }
+}
+
+impl<'list, 'rewritten, List, Item> PartialEq<[Item]> for WindowSlot<'list, 'rewritten, List>
+where
+    List: self::List + ?Sized,
+    List::Item: PartialEq<Item>,
+{
+    fn eq(&self, other: &[Item]) -> bool {
+        self.range.eq(other)
+    }
+}
+
+impl<'list, 'rewritten, List, Item, const N: usize> PartialEq<[Item; N]>
+    for WindowSlot<'list, 'rewritten, List>
+where
+    List: self::List + ?Sized,
+    List::Item: PartialEq<Item>,
+{
+    fn eq(&self, other: &[Item; N]) -> bool {
+        self.range.eq(other)
+    }
+}
+
+impl<'list, 'rewritten, List> Debug for WindowSlot<'list, 'rewritten, List>
+where
+    List: self::List + ?Sized,
+    List::Item: Debug,
+{
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.range.fmt(formatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_windows, edit_windows_with_limit, RewriteLimitError};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn visiting_every_window() {
+        let mut items = vec![1, 2, 3, 4];
+        let mut windows = Vec::new();
+
+        edit_windows(&mut items, 3, |window| {
+            windows.push(format!("{:?}", window))
+        })
+        .unwrap();
+
+        assert_eq!(windows, ["[1, 2, 3]", "[2, 3, 4]"]);
+    }
+
+    #[test]
+    fn visiting_no_windows_in_a_short_list() {
+        let mut items = vec![1, 2];
+        let mut visited = 0;
+
+        edit_windows(&mut items, 3, |_| visited += 1).unwrap();
+
+        assert_eq!(visited, 0);
+    }
+
+    #[test]
+    fn catching_opportunities_created_by_a_rewrite() {
+        let mut items = VecDeque::from(['(', '(', '[', ']', ')', ')', '(']);
+
+        let rewrites = edit_windows(&mut items, 2, |window| {
+            if window == ['(', ')'] || window == ['[', ']'] {
+                window.remove();
+            }
+        });
+
+        assert_eq!(items, VecDeque::from(['(']));
+        assert_eq!(rewrites, Ok(3));
+    }
+
+    #[test]
+    fn backing_up_from_the_start_of_the_list() {
+        let mut items = vec![1, 2, 3, 4];
+        let mut windows = Vec::new();
+
+        edit_windows(&mut items, 3, |window| {
+            windows.push(format!("{:?}", window));
+
+            if window == [2, 3, 4] {
+                window.replace([5, 6, 7, 8]);
+            }
+        })
+        .unwrap();
+
+        assert_eq!(items, vec![1, 5, 6, 7, 8]);
+        assert_eq!(
+            windows,
+            [
+                "[1, 2, 3]",
+                "[2, 3, 4]",
+                "[1, 5, 6]",
+                "[5, 6, 7]",
+                "[6, 7, 8]"
+            ]
+        );
+    }
+
+    #[test]
+    fn giving_up_on_endless_rewriting() {
+        let mut items = vec![1, 1, 1];
+
+        let result = edit_windows_with_limit(&mut items, 2, 5, |window| {
+            if window == [1, 1] {
+                window.replace([1, 1]);
+            }
+        });
+
+        assert_eq!(result, Err(RewriteLimitError { limit: 5 }));
+    }
+
+    #[test]
+    fn not_rewriting_past_the_limit() {
+        for limit in [0, 1, 5] {
+            let mut items = vec![0];
+
+            let result = edit_windows_with_limit(&mut items, 1, limit, |window| {
+                let next = window[0] + 1;
+                window.replace([next]);
+            });
+
+            assert_eq!(result, Err(RewriteLimitError { limit }));
+            assert_eq!(items, vec![limit]);
+        }
+    }
+
+    #[test]
+    fn giving_up_by_default() {
+        let mut items = vec![1, 1];
+
+        let result = edit_windows(&mut items, 2, |window| window.replace([1, 1]));
+
+        assert_eq!(result, Err(RewriteLimitError { limit: 48 }));
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn sliding_a_window_of_zero_items() {
+        let _ = edit_windows(&mut vec![1], 0, |_| {});
+    }
+}
@@ -0,0 +1,481 @@
+//! Editing parallel lists in lockstep, one row at a time.
+//!
+//! A [`Zip`] combines a tuple of mutable references to [`List`]s of equal length, like the
+//! columns of a table, into a list of rows. Rows inserted or removed through a [`RowSlot`] are
+//! inserted into or removed from every column.
+//!
+//! `Zip` doesn't implement `List` itself, because a row isn't stored anywhere as a single value
+//! that could be borrowed, so it can't be passed to [`edit`](crate::edit) or the other adapters.
+//! Instead, [`Zip::edit`] and [`Zip::try_edit`] visit rows with the same driver as `edit`, and a
+//! `RowSlot` borrows each column's item separately. `RowSlot` only supports inserting, replacing,
+//! removing and taking rows, not moving or pushing them.
+
+use crate::{List, Stride};
+use alloc::vec::Vec;
+use core::{
+    convert::Infallible,
+    fmt::{self, Display},
+};
+
+/// Combines `columns`, a tuple of mutable references to lists, into a list of rows.
+///
+/// ```
+/// use editer::zip::Zip;
+///
+/// let mut ids = vec![1, 2, 3];
+/// let mut names = vec!["a", "b", "c"];
+/// let mut scores = vec![0.5, 0.0, 0.9];
+///
+/// Zip::new((&mut ids, &mut names, &mut scores))
+///     .unwrap()
+///     .edit(|row| {
+///         let (_, name, score) = row.get();
+///
+///         if *score == 0.0 {
+///             row.remove();
+///         } else if *name == "c" {
+///             row.insert_after([(4, "d", 0.1)]);
+///         }
+///     });
+///
+/// assert_eq!(ids, vec![1, 3, 4]);
+/// assert_eq!(names, vec!["a", "c", "d"]);
+/// assert_eq!(scores, vec![0.5, 0.9, 0.1]);
+/// ```
+#[derive(Debug)]
+pub struct Zip<Columns> {
+    columns: Columns,
+}
+
+impl<Columns> Zip<Columns>
+where
+    Columns: self::Columns,
+{
+    /// Combines `columns` into a list of rows, or returns an error if they aren't all the same
+    /// length.
+    ///
+    /// ```
+    /// use editer::zip::{LengthMismatchError, Zip};
+    ///
+    /// let mut ids = vec![1, 2, 3];
+    /// let mut names = vec!["a", "b"];
+    ///
+    /// assert_eq!(
+    ///     Zip::new((&mut ids, &mut names)).unwrap_err(),
+    ///     LengthMismatchError { column: 1, len: 2, expected: 3 }
+    /// );
+    /// ```
+    pub fn new(columns: Columns) -> Result<Zip<Columns>, LengthMismatchError> {
+        columns.check_lens()?;
+        Ok(Zip { columns })
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns `true` if there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the rows, calling `edit` with a [`RowSlot`] for each row, as
+    /// [`edit`](crate::edit) does for the items of a list.
+    pub fn edit(&mut self, mut edit: impl FnMut(RowSlot<Columns>)) {
+        match self.try_edit(|row| {
+            edit(row);
+            Ok::<(), Infallible>(())
+        }) {
+            Ok(()) => {}
+            Err(never) => match never {},
+        }
+    }
+
+    /// Like [`Zip::edit`], but stops at the first error returned by `edit` and returns it, as
+    /// [`try_edit`](crate::try_edit) does.
+    ///
+    /// ```
+    /// use editer::zip::Zip;
+    ///
+    /// let mut ids = vec![1, 2, 3];
+    /// let mut names = vec!["a", "b", "c"];
+    ///
+    /// let result = Zip::new((&mut ids, &mut names)).unwrap().try_edit(|row| {
+    ///     let (id, name) = row.get();
+    ///
+    ///     if name.is_empty() {
+    ///         return Err(*id);
+    ///     }
+    ///
+    ///     row.insert_after([(0, "")]);
+    ///     Ok(())
+    /// });
+    ///
+    /// assert_eq!(result, Ok(()));
+    /// assert_eq!(ids, vec![1, 0, 2, 0, 3, 0]);
+    /// ```
+    pub fn try_edit<Error>(
+        &mut self,
+        mut edit: impl FnMut(RowSlot<Columns>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        crate::drive(
+            &mut self.columns,
+            &mut Stride::new(),
+            |columns| columns.len(),
+            |columns, index, stride| {
+                edit(RowSlot {
+                    columns,
+                    index,
+                    stride,
+                })
+            },
+        )
+    }
+
+    /// Returns the columns.
+    pub fn into_inner(self) -> Columns {
+        self.columns
+    }
+}
+
+/// A tuple of mutable references to lists, which [`Zip`] combines into a list of rows.
+///
+/// `Columns` is implemented for tuples of one to six mutable references to lists.
+#[allow(clippy::len_without_is_empty)]
+pub trait Columns {
+    /// A tuple with an item from each column.
+    type Row;
+
+    /// Returns the length of the first column.
+    fn len(&self) -> usize;
+
+    /// Returns an error if the columns aren't all the same length.
+    fn check_lens(&self) -> Result<(), LengthMismatchError>;
+
+    /// Inserts `rows` into the columns at `index`.
+    ///
+    /// The rows are split into columns, which are then inserted into one list at a time. If one
+    /// of the lists panics while inserting, the lists before it will already hold the new items,
+    /// but the lists after it won't.
+    fn insert(&mut self, index: usize, rows: Vec<Self::Row>);
+
+    /// Replaces the row at `index` with `rows`.
+    ///
+    /// Like [`insert`](Columns::insert), this updates one list at a time, so a panic part way
+    /// through leaves the lists with different lengths.
+    fn replace(&mut self, index: usize, rows: Vec<Self::Row>);
+
+    /// Removes and returns the row at `index`.
+    fn remove(&mut self, index: usize) -> Self::Row;
+}
+
+/// Borrows the items in a row of [`Columns`] for the lifetime `'a`.
+pub trait ColumnsRef<'a>: Columns {
+    /// A tuple with a shared reference to an item from each column.
+    type Ref;
+
+    /// A tuple with a mutable reference to an item from each column.
+    type Mut;
+
+    /// Returns shared references to the items in the row at `index`.
+    fn get(&'a self, index: usize) -> Self::Ref;
+
+    /// Returns mutable references to the items in the row at `index`.
+    fn get_mut(&'a mut self, index: usize) -> Self::Mut;
+}
+
+macro_rules! columns {
+    ($($list:ident $items:ident $item:ident $column:tt),+) => {
+        impl<'list, $($list),+> Columns for ($(&'list mut $list,)+)
+        where
+            $($list: List + ?Sized,)+
+        {
+            type Row = ($($list::Item,)+);
+
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            fn check_lens(&self) -> Result<(), LengthMismatchError> {
+                let expected = self.0.len();
+
+                $(
+                    if self.$column.len() != expected {
+                        return Err(LengthMismatchError {
+                            column: $column,
+                            len: self.$column.len(),
+                            expected,
+                        });
+                    }
+                )+
+
+                Ok(())
+            }
+
+            fn insert(&mut self, index: usize, rows: Vec<Self::Row>) {
+                $(let mut $items = Vec::with_capacity(rows.len());)+
+
+                for ($($item,)+) in rows {
+                    $($items.push($item);)+
+                }
+
+                $(self.$column.insert(index, $items.into_iter());)+
+            }
+
+            fn replace(&mut self, index: usize, rows: Vec<Self::Row>) {
+                $(let mut $items = Vec::with_capacity(rows.len());)+
+
+                for ($($item,)+) in rows {
+                    $($items.push($item);)+
+                }
+
+                $(self.$column.replace(index, $items.into_iter());)+
+            }
+
+            fn remove(&mut self, index: usize) -> Self::Row {
+                ($(self.$column.remove(index),)+)
+            }
+        }
+
+        impl<'a, 'list, $($list),+> ColumnsRef<'a> for ($(&'list mut $list,)+)
+        where
+            $($list: List + ?Sized + 'a,)+
+        {
+            type Ref = ($(&'a $list::Item,)+);
+            type Mut = ($(&'a mut $list::Item,)+);
+
+            fn get(&'a self, index: usize) -> Self::Ref {
+                ($(self.$column.index(index),)+)
+            }
+
+            fn get_mut(&'a mut self, index: usize) -> Self::Mut {
+                ($(self.$column.index_mut(index),)+)
+            }
+        }
+    };
+}
+
+columns!(A a_items a 0);
+columns!(A a_items a 0, B b_items b 1);
+columns!(A a_items a 0, B b_items b 1, C c_items c 2);
+columns!(A a_items a 0, B b_items b 1, C c_items c 2, D d_items d 3);
+columns!(A a_items a 0, B b_items b 1, C c_items c 2, D d_items d 3, E e_items e 4);
+columns!(A a_items a 0, B b_items b 1, C c_items c 2, D d_items d 3, E e_items e 4, F f_items f 5);
+
+/// The error returned by [`Zip::new`] when the columns aren't all the same length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthMismatchError {
+    /// The position in the tuple of the first column whose length differs from the first
+    /// column's.
+    pub column: usize,
+
+    /// The length of that column.
+    pub len: usize,
+
+    /// The length of the first column.
+    pub expected: usize,
+}
+
+impl Display for LengthMismatchError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "column {} has {} items, but column 0 has {}",
+            self.column, self.len, self.expected
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthMismatchError {}
+
+/// Represents the current row in a [`Zip::edit`].
+///
+/// If the `RowSlot` is dropped without calling any of its methods that take `self`, the row is
+/// left alone.
+pub struct RowSlot<'zip, 'stride, Columns> {
+    columns: &'zip mut Columns,
+    index: usize,
+    stride: &'stride mut Stride,
+}
+
+impl<'zip, 'stride, Columns> RowSlot<'zip, 'stride, Columns>
+where
+    Columns: self::Columns,
+{
+    /// Returns shared references to the items in the current row.
+    pub fn get<'a>(&'a self) -> <Columns as ColumnsRef<'a>>::Ref
+    where
+        Columns: ColumnsRef<'a>,
+    {
+        self.columns.get(self.index)
+    }
+
+    /// Returns mutable references to the items in the current row.
+    ///
+    /// ```
+    /// use editer::zip::Zip;
+    ///
+    /// let mut names = vec!["a", "b"];
+    /// let mut counts = vec![1, 2];
+    ///
+    /// Zip::new((&mut names, &mut counts)).unwrap().edit(|mut row| {
+    ///     let (name, count) = row.get_mut();
+    ///
+    ///     if *name == "b" {
+    ///         *count *= 10;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(counts, vec![1, 20]);
+    /// ```
+    pub fn get_mut<'a>(&'a mut self) -> <Columns as ColumnsRef<'a>>::Mut
+    where
+        Columns: ColumnsRef<'a>,
+    {
+        self.columns.get_mut(self.index)
+    }
+
+    /// Inserts zero or more `rows` before the current row.
+    pub fn insert_before(self, rows: impl IntoIterator<Item = Columns::Row>) {
+        let rows: Vec<_> = rows.into_iter().collect();
+        self.stride.set(rows.len() + 1);
+        self.columns.insert(self.index, rows);
+    }
+
+    /// Inserts zero or more `rows` after the current row.
+    pub fn insert_after(self, rows: impl IntoIterator<Item = Columns::Row>) {
+        let rows: Vec<_> = rows.into_iter().collect();
+        self.stride.set(rows.len() + 1);
+        self.columns.insert(self.index + 1, rows);
+    }
+
+    /// Replaces the current row with zero or more `rows`.
+    pub fn replace(self, rows: impl IntoIterator<Item = Columns::Row>) {
+        let rows: Vec<_> = rows.into_iter().collect();
+        self.stride.set(rows.len());
+        self.columns.replace(self.index, rows);
+    }
+
+    /// Removes the current row.
+    pub fn remove(self) {
+        self.take();
+    }
+
+    /// Removes the current row and returns its items.
+    pub fn take(self) -> Columns::Row {
+        self.stride.set(0);
+        self.columns.remove(self.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LengthMismatchError, Zip};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn editing_rows_in_lockstep() {
+        let mut ids = vec![1, 2, 3, 4];
+        let mut names = VecDeque::from(["a", "b", "c", "d"]);
+        let mut visited = Vec::new();
+
+        Zip::new((&mut ids, &mut names)).unwrap().edit(|row| {
+            let (id, name) = row.get();
+            visited.push((*id, *name));
+
+            match id {
+                1 => row.insert_before([(0, "z")]),
+                2 => row.replace([(5, "e"), (6, "f")]),
+                3 => row.remove(),
+                _ => row.insert_after([(7, "g")]),
+            }
+        });
+
+        assert_eq!(visited, [(1, "a"), (2, "b"), (3, "c"), (4, "d")]);
+        assert_eq!(ids, vec![0, 1, 5, 6, 4, 7]);
+        assert_eq!(names, VecDeque::from(["z", "a", "e", "f", "d", "g"]));
+    }
+
+    #[test]
+    fn taking_rows() {
+        let mut ids = vec![1, 2];
+        let mut flags = vec![false, true];
+        let mut taken = Vec::new();
+
+        Zip::new((&mut ids, &mut flags)).unwrap().edit(|row| {
+            if *row.get().1 {
+                taken.push(row.take());
+            }
+        });
+
+        assert_eq!(taken, [(2, true)]);
+        assert_eq!(ids, vec![1]);
+        assert_eq!(flags, vec![false]);
+    }
+
+    #[test]
+    fn stopping_at_the_first_error() {
+        let mut ids = vec![1, 2, 3, 4];
+        let mut names = vec!["a", "b", "", "d"];
+        let mut visited = Vec::new();
+
+        let result = Zip::new((&mut ids, &mut names)).unwrap().try_edit(|row| {
+            let (id, name) = row.get();
+            visited.push(*id);
+
+            if name.is_empty() {
+                return Err(*id);
+            }
+
+            row.remove();
+            Ok(())
+        });
+
+        assert_eq!(result, Err(3));
+        assert_eq!(visited, [1, 2, 3]);
+        assert_eq!(ids, vec![3, 4]);
+        assert_eq!(names, vec!["", "d"]);
+    }
+
+    #[test]
+    fn zipping_a_single_column() {
+        let mut items = vec![1, 2, 3];
+
+        let mut zip = Zip::new((&mut items,)).unwrap();
+        zip.edit(|mut row| *row.get_mut().0 *= 2);
+
+        assert_eq!(zip.len(), 3);
+        assert_eq!(items, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn rejecting_columns_of_different_lengths() {
+        let mut a = vec![1, 2];
+        let mut b = vec![1, 2];
+        let mut c = vec![1];
+
+        assert_eq!(
+            Zip::new((&mut a, &mut b, &mut c)).unwrap_err(),
+            LengthMismatchError {
+                column: 2,
+                len: 1,
+                expected: 2
+            }
+        );
+    }
+
+    #[test]
+    fn zipping_empty_columns() {
+        let mut a: Vec<i32> = Vec::new();
+        let mut b: Vec<i32> = Vec::new();
+        let mut visited = 0;
+
+        let mut zip = Zip::new((&mut a, &mut b)).unwrap();
+        zip.edit(|_| visited += 1);
+
+        assert!(zip.is_empty());
+        assert_eq!(visited, 0);
+    }
+}